@@ -0,0 +1,264 @@
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// # CancelToken
+///
+/// `World` の協調的な停止を実現するためのキャンセルトークンです。
+///
+/// `World::with_cancel` で作成されたWorldのクロージャはこのトークンを受け取り、
+/// `is_cancelled()` や `sleep_or_cancel()` を使って停止要求に応答できます。
+/// `World::stop`、`Multiverse::kill`、`Multiverse::stop_all` はいずれもこのトークンをキャンセルします。
+///
+/// トークンは `Clone` で複製でき、複製はすべて同じキャンセル状態を共有します。
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    signal: Signal,
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CancelToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl CancelToken {
+    /// キャンセルされていない新しいトークンを作成します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// assert!(!token.is_cancelled());
+    /// ```
+    pub fn new() -> Self {
+        CancelToken {
+            signal: Signal::new(),
+        }
+    }
+
+    /// トークンがキャンセルされているかどうかを返します。
+    pub fn is_cancelled(&self) -> bool {
+        self.signal.is_fired()
+    }
+
+    /// トークンをキャンセルします。
+    ///
+    /// `sleep_or_cancel` で待機中のスレッドを起こし、`on_cancel` で登録されたコールバックを
+    /// 呼び出し元のスレッドで実行します。既にキャンセル済みの場合は何もしません。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// let clone = token.clone();
+    /// token.cancel();
+    /// assert!(clone.is_cancelled()); // 複製も同じ状態を共有する
+    /// ```
+    pub fn cancel(&self) {
+        self.signal.fire();
+    }
+
+    /// 指定された時間だけスリープします。途中でキャンセルされた場合はすぐに戻ります。
+    ///
+    /// # 戻り値
+    /// キャンセルされた（またはスリープ前から既にキャンセル済みだった）場合は `true`、
+    /// 指定時間を最後までスリープした場合は `false` を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::CancelToken;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let token = CancelToken::new();
+    /// assert!(!token.sleep_or_cancel(Duration::from_millis(5)));
+    ///
+    /// token.cancel();
+    /// let started = Instant::now();
+    /// assert!(token.sleep_or_cancel(Duration::from_secs(10)));
+    /// assert!(started.elapsed() < Duration::from_secs(1));
+    /// ```
+    pub fn sleep_or_cancel(&self, dur: Duration) -> bool {
+        self.signal.wait_timeout(dur)
+    }
+
+    /// キャンセル時に一度だけ呼び出されるコールバックを登録します。
+    ///
+    /// 既にキャンセル済みの場合、コールバックはこの呼び出しの中で即座に実行されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::CancelToken;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// let token = CancelToken::new();
+    /// let called = Arc::new(AtomicBool::new(false));
+    /// let called_clone = Arc::clone(&called);
+    /// token.on_cancel(move || called_clone.store(true, Ordering::SeqCst));
+    ///
+    /// assert!(!called.load(Ordering::SeqCst));
+    /// token.cancel();
+    /// assert!(called.load(Ordering::SeqCst));
+    /// ```
+    pub fn on_cancel<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.signal.on_fire(callback);
+    }
+}
+
+/// 一度だけ発火するシグナル（ラッチ）です。
+///
+/// `CancelToken` のキャンセル通知や、`World` の完了通知に使用されます。
+/// 複製はすべて同じ状態を共有します。
+#[derive(Clone)]
+pub(crate) struct Signal {
+    inner: Arc<SignalInner>,
+}
+
+struct SignalInner {
+    /// 発火状態と、発火時に実行するコールバックの一覧
+    state: Mutex<SignalState>,
+    /// `wait` / `wait_timeout` で待機しているスレッドを起こすための条件変数
+    condvar: Condvar,
+}
+
+struct SignalState {
+    fired: bool,
+    callbacks: Vec<Box<dyn FnOnce() + Send + 'static>>,
+}
+
+impl Default for Signal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Signal {
+    pub(crate) fn new() -> Self {
+        Signal {
+            inner: Arc::new(SignalInner {
+                state: Mutex::new(SignalState {
+                    fired: false,
+                    callbacks: Vec::new(),
+                }),
+                condvar: Condvar::new(),
+            }),
+        }
+    }
+
+    pub(crate) fn is_fired(&self) -> bool {
+        self.inner.state.lock().unwrap().fired
+    }
+
+    /// シグナルを発火させ、待機中のスレッドを起こし、登録されたコールバックを実行します。
+    /// 既に発火済みの場合は何もしません。
+    pub(crate) fn fire(&self) {
+        let callbacks = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.fired {
+                return;
+            }
+            state.fired = true;
+            std::mem::take(&mut state.callbacks)
+        };
+        self.inner.condvar.notify_all();
+        // コールバックはロックを解放してから実行する（コールバック内からシグナルを参照できるように）
+        for callback in callbacks {
+            callback();
+        }
+    }
+
+    /// 発火するまで待機します。
+    pub(crate) fn wait(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        while !state.fired {
+            state = self.inner.condvar.wait(state).unwrap();
+        }
+    }
+
+    /// 発火するか `dur` が経過するまで待機します。発火していれば `true` を返します。
+    pub(crate) fn wait_timeout(&self, dur: Duration) -> bool {
+        let deadline = Instant::now() + dur;
+        let mut state = self.inner.state.lock().unwrap();
+        while !state.fired {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            state = self
+                .inner
+                .condvar
+                .wait_timeout(state, deadline - now)
+                .unwrap()
+                .0;
+        }
+        true
+    }
+
+    /// 発火時に一度だけ呼び出されるコールバックを登録します。既に発火済みの場合は即座に実行します。
+    pub(crate) fn on_fire<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.fired {
+            drop(state);
+            callback();
+        } else {
+            state.callbacks.push(Box::new(callback));
+        }
+    }
+}
+
+/// 完了通知で起こされる `Waker` と、コールバックを登録した実行の番号の置き場所です
+/// （`World::join` などのFutureが保持します）。
+pub(crate) type WakerSlot = Option<(usize, Arc<Mutex<Waker>>)>;
+
+/// `try_result` で結果を確認し、まだ得られなければ、完了時に現在のタスクが起こされるようにします。
+///
+/// 完了時のコールバックは実行ごとに一度だけ `on_complete` に登録し、以降は `Waker` だけを差し替えます。
+/// `run` が返す実行の番号（`reset` された回数）が登録時から変わっていれば、新しい実行に登録し直します。
+/// 古い実行のコールバックは、呼ばれずに破棄された場合もタスクを起こすため、登録し直す機会を逃しません。
+/// 結果を確認する前に `Waker` を登録するため、その間に完了しても通知を取りこぼしません。
+pub(crate) fn poll_completion<T>(
+    cx: &mut Context<'_>,
+    slot: &mut WakerSlot,
+    run: impl FnOnce() -> usize,
+    try_result: impl FnOnce() -> Option<T>,
+    on_complete: impl FnOnce(Box<dyn FnOnce() + Send + 'static>),
+) -> Poll<T> {
+    // 登録より先に番号を読むため、その間に reset されても次のポーリングで登録し直される
+    let current = run();
+    match slot {
+        Some((registered, waker)) if *registered == current => {
+            waker.lock().unwrap().clone_from(cx.waker())
+        }
+        _ => {
+            let waker = Arc::new(Mutex::new(cx.waker().clone()));
+            *slot = Some((current, Arc::clone(&waker)));
+            let wake = WakeOnDrop(waker);
+            on_complete(Box::new(move || drop(wake)));
+        }
+    }
+    match try_result() {
+        Some(result) => Poll::Ready(result),
+        None => Poll::Pending,
+    }
+}
+
+/// 破棄されたときにタスクを起こします。コールバックが呼ばれた場合も、呼ばれずに破棄された場合も同様です。
+struct WakeOnDrop(Arc<Mutex<Waker>>);
+
+impl Drop for WakeOnDrop {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().wake_by_ref();
+    }
+}
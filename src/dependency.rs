@@ -0,0 +1,201 @@
+use crate::error::WorldError;
+use crate::failure::Failure;
+use crate::world::{AnyWorld, WorldStatus};
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// 上流の完了後に実行開始された下流のWorldに渡すために、上流のWorldから取り出した結果（IDごと）
+///
+/// 呼び出し側は、取り出された後も `Multiverse::status` などでこの結果を取得できます。
+pub(crate) type ForwardedResults = Arc<Mutex<HashMap<String, Box<dyn Any + Send>>>>;
+
+/// 依存するWorldが失敗（`Finished` 以外で終了）したときの扱いを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DependencyPolicy {
+    /// 実行せずに `WorldStatus::Skipped` にします（既定）。
+    #[default]
+    Skip,
+    /// 実行せずに `WorldStatus::Failed` にします。
+    /// 失敗の元のエラーは `WorldError::DependencyFailed` です。
+    Fail,
+    /// 失敗を無視し、すべての依存先が終了するのを待ってから実行します。
+    RunAnyway,
+}
+
+/// あるWorldが依存するWorldのIDと、依存先が失敗したときの扱い
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Dependencies {
+    pub(crate) ids: Vec<String>,
+    pub(crate) policy: DependencyPolicy,
+}
+
+/// `id` が `deps` に依存するようにしたときに循環が生じるかを調べます。
+///
+/// 循環が生じる場合は、`id` から始まり `id` に戻る経路を返します。
+/// 依存グラフには、まだ追加されていないWorldのIDが含まれていても構いません。
+pub(crate) fn find_cycle(
+    graph: &HashMap<String, Dependencies>,
+    id: &str,
+    deps: &[String],
+) -> Option<Vec<String>> {
+    let mut visited = HashSet::new();
+    for dep in deps {
+        let mut path = vec![id.to_string()];
+        if reaches(graph, dep, id, &mut path, &mut visited) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// `from` から依存をたどって `target` に到達できるかを深さ優先で調べ、到達した経路を `path` に残します。
+fn reaches<'a>(
+    graph: &'a HashMap<String, Dependencies>,
+    from: &'a str,
+    target: &str,
+    path: &mut Vec<String>,
+    visited: &mut HashSet<&'a str>,
+) -> bool {
+    path.push(from.to_string());
+    if from == target {
+        return true;
+    }
+    if visited.insert(from)
+        && let Some(dependencies) = graph.get(from)
+    {
+        for next in &dependencies.ids {
+            if reaches(graph, next, target, path, visited) {
+                return true;
+            }
+        }
+    }
+    path.pop();
+    false
+}
+
+/// 依存するWorldがすべて終了したときに、`policy` に従って `world` を実行開始または終了させるようにします。
+///
+/// 待機のためのスレッドは作成せず、依存先が完了するたびにその完了を通知したスレッドで状態を確認します。
+/// 待機中に `world` が停止された場合は何もしません。
+///
+/// `world` が上流の結果を受け取るWorld（`World::after` など）であれば、上流が結果を返す前に結果を複製して受け取ります。
+/// 上流が既に結果を返していた場合は、ここで上流の結果を取り出して `forwarded` に保持しておきます。
+pub(crate) fn await_dependencies(
+    world: Arc<dyn AnyWorld>,
+    deps: Vec<(String, Arc<dyn AnyWorld>)>,
+    policy: DependencyPolicy,
+    forwarded: ForwardedResults,
+) {
+    for (index, id) in world.any_upstream_ids().into_iter().enumerate() {
+        let Some((_, dep)) = deps.iter().find(|(dep_id, _)| *dep_id == id) else {
+            continue;
+        };
+        let world = Arc::clone(&world);
+        let type_name = dep.result_type_name();
+        let registered = dep.any_on_result(Box::new(move |value| {
+            // 型が一致しない場合のエラーは保持され、依存先の確認時に失敗として扱われる
+            let _ = world.any_bind_input(index, value, type_name);
+        }));
+        if !registered && dep.any_progress() == WorldStatus::Finished {
+            take_result(&id, dep.as_ref(), &forwarded);
+        }
+    }
+
+    let deps = Arc::new(deps);
+    for (_, dep) in deps.iter() {
+        let world = Arc::clone(&world);
+        let deps = Arc::clone(&deps);
+        let forwarded = Arc::clone(&forwarded);
+        // 既に完了している依存先のコールバックは、ここですぐに実行される
+        dep.any_on_complete(Box::new(move || {
+            resolve(world.as_ref(), &deps, policy, &forwarded)
+        }));
+    }
+}
+
+/// 依存先の状態を確認し、すべて終了していれば `world` を実行開始または終了させます。
+///
+/// 複数の依存先が同時に完了して並行に呼び出されても、`Blocked` から抜けられるのは一度だけです。
+fn resolve(
+    world: &dyn AnyWorld,
+    deps: &[(String, Arc<dyn AnyWorld>)],
+    policy: DependencyPolicy,
+    forwarded: &Mutex<HashMap<String, Box<dyn Any + Send>>>,
+) {
+    if world.any_progress() != WorldStatus::Blocked {
+        return;
+    }
+
+    let mut pending = false;
+    let mut failed = None;
+    for (id, dep) in deps {
+        match dep.any_progress() {
+            WorldStatus::Finished => {}
+            status if status == WorldStatus::Ready || status.is_active() => pending = true,
+            _ => {
+                failed.get_or_insert(id);
+            }
+        }
+    }
+
+    match (failed, policy) {
+        (Some(_), DependencyPolicy::Skip) => world.any_abandon(WorldStatus::Skipped),
+        (Some(id), DependencyPolicy::Fail) => {
+            let failure = Failure::from_error(WorldError::DependencyFailed(id.clone()));
+            world.any_abandon(WorldStatus::Failed(failure));
+        }
+        _ if pending => {} // 残りの依存先が完了したときに、再び確認する
+        _ => {
+            if let Err(error) = forward_results(world, deps, forwarded) {
+                let failure = Failure::from_error(error);
+                return world.any_abandon(WorldStatus::Failed(failure));
+            }
+            let _ = world.any_unblock(); // 待機中に停止されていればエラーになるが、無視する
+        }
+    }
+}
+
+/// 既に結果を返した上流のWorldから結果を取り出し、`forwarded` に保持します。
+///
+/// 既に保持している場合や、結果が既に取得されていた場合は何もしません。
+fn take_result(
+    id: &str,
+    dep: &dyn AnyWorld,
+    forwarded: &Mutex<HashMap<String, Box<dyn Any + Send>>>,
+) {
+    if forwarded.lock().unwrap().contains_key(id) {
+        return;
+    }
+    // 結果は送信済みか、送信される直前なので、すぐに受け取れる
+    if let Ok(result) = dep.any_status() {
+        forwarded.lock().unwrap().insert(id.to_string(), result);
+    }
+}
+
+/// 上流のWorldの結果を `world` に引数として渡します。
+///
+/// 上流が結果を返す前に受け取っていなかった結果は、`forwarded` に保持されているものを使います。
+fn forward_results(
+    world: &dyn AnyWorld,
+    deps: &[(String, Arc<dyn AnyWorld>)],
+    forwarded: &Mutex<HashMap<String, Box<dyn Any + Send>>>,
+) -> Result<(), WorldError> {
+    let forwarded_guard = forwarded.lock().unwrap();
+    for (index, id) in world.any_upstream_ids().iter().enumerate() {
+        if let Some(bound) = world.any_input(index) {
+            bound?;
+            continue;
+        }
+        let (_, dep) = deps
+            .iter()
+            .find(|(dep_id, _)| dep_id == id)
+            .ok_or_else(|| WorldError::NotFound(id.clone()))?;
+        // 失敗した上流（DependencyPolicy::RunAnyway の場合）や、結果が既に取得されていた上流からは受け取れない
+        let Some(result) = forwarded_guard.get(id) else {
+            return Err(WorldError::DependencyFailed(id.clone()));
+        };
+        world.any_bind_input(index, result.as_ref(), dep.result_type_name())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::failure::Failure;
+use std::error::Error;
+use std::fmt;
+
+/// `World` および `Multiverse` の操作で発生するエラーを表す列挙型
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorldError {
+    /// 指定されたIDのWorldが見つかりません。
+    NotFound(String),
+    /// 同じIDのWorldが既に存在します。
+    AlreadyExists(String),
+    /// Worldは既に実行中です（実行中のWorldに対して許可されない操作を含みます）。
+    AlreadyRunning,
+    /// Worldは既に完了または失敗しており、再実行できません。
+    /// `World::repeatable` で作成されたWorldは、`World::reset` で `Ready` に戻してから再実行できます。
+    AlreadyCompleted,
+    /// Worldは実行中ではありません。
+    NotRunning,
+    /// Worldに実行する関数が設定されていません。
+    NoProcess,
+    /// 待機対象のWorldが1つも指定されていません。
+    NoWorlds,
+    /// Worldの実行中にパニックが発生しました。
+    Panicked {
+        /// パニックのペイロードを文字列化したもの
+        payload: String,
+        /// パニック発生時に取得されたバックトレース（取得できた場合）
+        backtrace: Option<String>,
+    },
+    /// `World::try_from` のクロージャがエラーを返しました。
+    /// 元のエラーは `Failure::downcast_error` で取り出せます。
+    Failed(Failure),
+    /// Worldは完了する前に停止されました。
+    Stopped,
+    /// Worldは完了する前に強制終了されました。
+    Killed,
+    /// 依存するWorldが失敗したため、Worldは実行されませんでした。
+    Skipped,
+    /// 依存するWorldが失敗したため、Worldは失敗として扱われました。失敗した依存先のIDを保持します。
+    DependencyFailed(String),
+    /// `World::after` / `World::after2` で作成されたWorldに、上流のWorldの結果が渡されませんでした。
+    InputsNotProvided,
+    /// 依存関係を追加すると循環が生じます。循環する経路のIDを順に保持します。
+    DependencyCycle(Vec<String>),
+    /// Worldの結果（またはストリームのアイテム・受信箱のメッセージ）を期待された型にダウンキャストできませんでした。
+    DowncastFailed {
+        /// 呼び出し側が期待した型の名前
+        expected: &'static str,
+        /// Worldが実際に返す型の名前
+        actual: &'static str,
+    },
+    /// Worldの結果は既に取得されています。
+    ResultAlreadyTaken,
+    /// `Multiverse::wait_any` / `Multiverse::wait_all` で待機中のWorldが `reset` され、
+    /// 待っていた実行の結果が得られなくなりました。そのWorldのIDを保持します。
+    WaitAbandoned(String),
+    /// Worldは `World::with_emitter` で作成されていないため、ストリームがありません。
+    NoStream,
+    /// Worldのストリームは既に取り出されています。
+    StreamAlreadyTaken,
+    /// Worldは `World::with_inbox` で作成されていないため、受信箱がありません。
+    NoInbox,
+    /// Worldの受信箱は閉じられています（Worldが停止・完了しています）。
+    InboxClosed,
+    /// Worldは制限時間内に結果を返しませんでした。
+    TimedOut,
+    /// Worldは `World::repeatable` で作成されていないため、再実行できません。
+    NotRepeatable,
+    /// `Supervisor` が制限時間内に許容回数を超えて子Worldを再起動しようとしたため、監視を断念しました。
+    /// 断念の原因となった子WorldのIDを保持します。
+    RestartLimitExceeded(String),
+}
+
+impl fmt::Display for WorldError {
+    /// `WorldError` を人間が読める文字列形式にフォーマットします。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::WorldError;
+    ///
+    /// assert_eq!(
+    ///     WorldError::NotFound("task".to_string()).to_string(),
+    ///     "World with ID 'task' not found."
+    /// );
+    /// assert_eq!(WorldError::TimedOut.to_string(), "World timed out.");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorldError::NotFound(id) => write!(f, "World with ID '{}' not found.", id),
+            WorldError::AlreadyExists(id) => write!(f, "World with ID '{}' already exists.", id),
+            WorldError::AlreadyRunning => write!(f, "World is already running."),
+            WorldError::AlreadyCompleted => write!(
+                f,
+                "World has already completed or failed and cannot be restarted."
+            ),
+            WorldError::NotRunning => write!(f, "World is not running or already stopped."),
+            WorldError::NoProcess => write!(f, "No process defined for this World."),
+            WorldError::NoWorlds => write!(f, "No Worlds given to wait for."),
+            WorldError::Panicked { payload, .. } => write!(f, "Thread panicked: {}", payload),
+            WorldError::Failed(failure) => write!(f, "World failed: {}", failure),
+            WorldError::Stopped => write!(f, "World was stopped before completion."),
+            WorldError::Killed => write!(f, "World was killed before completion."),
+            WorldError::Skipped => write!(f, "World was skipped because a dependency failed."),
+            WorldError::DependencyFailed(id) => write!(f, "Dependency '{}' failed.", id),
+            WorldError::InputsNotProvided => {
+                write!(f, "Upstream results were not provided to this World.")
+            }
+            WorldError::DependencyCycle(path) => write!(
+                f,
+                "Adding these dependencies would create a cycle: {}.",
+                path.join(" -> ")
+            ),
+            WorldError::DowncastFailed { expected, actual } => write!(
+                f,
+                "Failed to downcast World result of type '{}' to expected type '{}'.",
+                actual, expected
+            ),
+            WorldError::ResultAlreadyTaken => write!(f, "World result already retrieved."),
+            WorldError::WaitAbandoned(id) => {
+                write!(f, "World with ID '{}' was reset while being waited on.", id)
+            }
+            WorldError::NoStream => write!(f, "World does not emit a stream."),
+            WorldError::StreamAlreadyTaken => write!(f, "World stream already taken."),
+            WorldError::NoInbox => write!(f, "World has no inbox."),
+            WorldError::InboxClosed => write!(f, "World inbox is closed."),
+            WorldError::TimedOut => write!(f, "World timed out."),
+            WorldError::NotRepeatable => write!(f, "World is not repeatable and cannot be reset."),
+            WorldError::RestartLimitExceeded(id) => write!(
+                f,
+                "Supervisor gave up after too many restarts of World '{}'.",
+                id
+            ),
+        }
+    }
+}
+
+impl Error for WorldError {}
@@ -0,0 +1,17 @@
+use crate::world::WorldStatus;
+use std::time::Instant;
+
+/// # WorldEvent
+///
+/// `Multiverse` に登録されたWorldの状態の変化を表すイベントです（`Multiverse::subscribe` を参照）。
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldEvent {
+    /// 状態が変化したWorldのID
+    pub id: String,
+    /// 変化前の状態
+    pub from: WorldStatus,
+    /// 変化後の状態
+    pub to: WorldStatus,
+    /// 状態が変化した時刻
+    pub at: Instant,
+}
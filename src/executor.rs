@@ -0,0 +1,493 @@
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Wake, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 実行器に渡されるタスク
+pub type Task = Box<dyn FnOnce() + Send + 'static>;
+
+/// # Executor
+///
+/// Worldのタスクを実行するスレッドを決める、差し替え可能な実行器です。
+///
+/// `execute` は渡されたタスクを（呼び出し元とは別のスレッドで）いずれ一度だけ実行しなければなりません。
+/// タスクを実行せずに破棄すると、そのWorldは `WorldStatus::Queued` のまま終了しなくなります。
+/// `World::from_future` で作成されたWorldのFutureは、起こされるたびに一度だけポーリングするタスクとして渡されます。
+///
+/// # 例
+/// ```
+/// use parallel_world::executor::Task;
+/// use parallel_world::{Executor, Multiverse, World};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::thread;
+///
+/// /// タスクごとにスレッドを作成し、実行したタスクの数を数える実行器
+/// struct Counting(AtomicUsize);
+///
+/// impl Executor for Counting {
+///     fn execute(&self, task: Task) {
+///         self.0.fetch_add(1, Ordering::SeqCst);
+///         thread::spawn(task);
+///     }
+/// }
+///
+/// let mv = Multiverse::with_executor(Counting(AtomicUsize::new(0)));
+/// mv.add("a".to_string(), World::from(|| 1)).unwrap();
+/// mv.add("b".to_string(), World::from(|| 2)).unwrap();
+/// mv.start_all();
+/// assert_eq!(mv.status::<i32>("a"), Ok(1));
+/// assert_eq!(mv.status::<i32>("b"), Ok(2));
+/// ```
+pub trait Executor: Send + Sync {
+    /// タスクを実行します。
+    fn execute(&self, task: Task);
+
+    /// 優先度付きでタスクを実行します。
+    ///
+    /// `priority` はタスクが待機している間にも `Multiverse::set_priority` などで変更されることがあるため、
+    /// 実行するタスクを選ぶ時点で `Priority::get` を呼び出してください。
+    /// 既定の実装は優先度を無視して `execute` を呼び出します。
+    fn execute_with_priority(&self, task: Task, priority: Priority) {
+        let _ = priority;
+        self.execute(task);
+    }
+}
+
+/// # Priority
+///
+/// Worldの実行の優先度（値が大きいほど先に実行されます）です。
+///
+/// 同じWorldの `Priority` はすべて同じ値を共有するため、実行器に渡された後の変更も反映されます。
+#[derive(Debug, Clone, Default)]
+pub struct Priority(Arc<PriorityInner>);
+
+#[derive(Debug, Default)]
+struct PriorityInner {
+    value: AtomicI32,
+    /// この優先度のタスクが待機している `ThreadPool` のキューの、優先度の変更を知らせるフラグ
+    /// （タスクが待機している間だけ登録されます）
+    queued_in: Mutex<Vec<Arc<AtomicBool>>>,
+}
+
+impl Priority {
+    /// 指定された値の優先度を作成します。
+    pub fn new(value: i32) -> Self {
+        Priority(Arc::new(PriorityInner {
+            value: AtomicI32::new(value),
+            queued_in: Mutex::new(Vec::new()),
+        }))
+    }
+
+    /// 現在の優先度を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::executor::Priority;
+    ///
+    /// let priority = Priority::new(3);
+    /// let shared = priority.clone();
+    /// priority.set(10);
+    /// assert_eq!(shared.get(), 10);
+    /// ```
+    pub fn get(&self) -> i32 {
+        self.0.value.load(Ordering::SeqCst)
+    }
+
+    /// 優先度を変更します。この `Priority` を複製したものにも反映されます。
+    pub fn set(&self, value: i32) {
+        self.0.value.store(value, Ordering::SeqCst);
+        // 待機中のキューにだけ、順序を計算し直すよう知らせる
+        for changed in self.0.queued_in.lock().unwrap().iter() {
+            changed.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// この優先度のタスクが、`changed` で変更を受け取るキューで待機し始めたことを記録します。
+    fn enqueue(&self, changed: &Arc<AtomicBool>) {
+        self.0.queued_in.lock().unwrap().push(Arc::clone(changed));
+    }
+
+    /// `enqueue` で記録したキューでの待機が終わったことを記録します。
+    fn dequeue(&self, changed: &Arc<AtomicBool>) {
+        let mut queued_in = self.0.queued_in.lock().unwrap();
+        if let Some(index) = queued_in.iter().position(|c| Arc::ptr_eq(c, changed)) {
+            queued_in.swap_remove(index);
+        }
+    }
+}
+
+/// # ThreadPool
+///
+/// 固定数のワーカースレッドでタスクを実行する実行器です。
+///
+/// ワーカーがすべて使用中の間、タスクはキューで待機します（待機中のWorldは `WorldStatus::Queued` です）。
+/// ワーカーが空くと、キューの中で優先度（`World::with_priority`）が最も高いタスクを実行します。
+/// 優先度が同じタスクは到着順に実行されます。
+/// `with_aging` を設定すると、待機時間に比例して優先度が上がり、優先度の低いタスクがいつまでも実行されない
+/// 事態（飢餓）を防げます。
+///
+/// `ThreadPool` が破棄されると、ワーカーはキューに残ったタスクを実行し終えてから終了します。
+///
+/// # 例
+/// ```
+/// use parallel_world::{Multiverse, ThreadPool, World, WorldStatus};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// let mv = Multiverse::with_executor(ThreadPool::new(2));
+/// for i in 0..4 {
+///     mv.add(format!("world{i}"), World::from(move || {
+///         sleep(Duration::from_millis(100));
+///         i
+///     })).unwrap();
+/// }
+/// mv.start_all();
+/// sleep(Duration::from_millis(30));
+///
+/// // 同時に実行されるのは2つだけで、残りはワーカーが空くのを待っている
+/// let statuses: Vec<_> = (0..4).map(|i| mv.progress(&format!("world{i}")).unwrap()).collect();
+/// assert_eq!(statuses.iter().filter(|s| **s == WorldStatus::Running).count(), 2);
+/// assert_eq!(statuses.iter().filter(|s| **s == WorldStatus::Queued).count(), 2);
+///
+/// for i in 0..4 {
+///     assert_eq!(mv.status::<i32>(&format!("world{i}")), Ok(i));
+/// }
+/// ```
+///
+/// 優先度の高いWorldは、先に開始された優先度の低いWorldより先に実行されます。
+/// ```
+/// use parallel_world::{Multiverse, ThreadPool, World};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// let mv = Multiverse::with_executor(ThreadPool::new(1));
+/// let order = Arc::new(Mutex::new(Vec::new()));
+/// mv.add("busy".to_string(), World::from(|| sleep(Duration::from_millis(50)))).unwrap();
+/// for (id, priority) in [("batch1", 0), ("batch2", 0), ("interactive", 10)] {
+///     let order = Arc::clone(&order);
+///     let world = World::from(move || order.lock().unwrap().push(id)).with_priority(priority);
+///     mv.add(id.to_string(), world).unwrap();
+/// }
+///
+/// mv.exec("busy").unwrap(); // 唯一のワーカーを使用中にする
+/// mv.start_all();
+/// mv.wait_all(&["busy", "batch1", "batch2", "interactive"]).unwrap();
+/// assert_eq!(order.lock().unwrap()[0], "interactive");
+/// ```
+pub struct ThreadPool {
+    /// ワーカーと共有する状態
+    shared: Arc<Shared>,
+    /// ワーカースレッドの数
+    size: usize,
+}
+
+/// `ThreadPool` とワーカーが共有する状態
+struct Shared {
+    queue: Mutex<Queue>,
+    /// タスクが追加されたとき、または `ThreadPool` が破棄されたときに通知される
+    available: Condvar,
+}
+
+/// 実行を待っているタスクのキュー
+///
+/// エージングによる優先度の上がり方はすべてのタスクで同じため、実効優先度の大小は時間が経っても変わりません。
+/// そこで、追加した時点で順序のキーを求めてヒープに保持し、取り出すたびに全体を計算し直すことはしません。
+/// このキューで待機しているタスクの優先度が変更された場合だけ、次に取り出すときにキーを計算し直します。
+struct Queue {
+    entries: BinaryHeap<Entry>,
+    /// 待機時間に応じて優先度を1ずつ上げる間隔（`None` の場合は上げない）
+    aging: Option<Duration>,
+    /// 待機時間を測る基準の時刻
+    epoch: Instant,
+    /// 次に追加するタスクの通し番号（優先度が同じタスクを到着順に取り出すため）
+    next_seq: u64,
+    /// 待機中のタスクの優先度が変更されたかどうか（`Priority::set` で設定されます）
+    changed: Arc<AtomicBool>,
+    /// `ThreadPool` が破棄されたかどうか
+    closed: bool,
+}
+
+/// キューで待機しているタスク
+struct Entry {
+    task: Task,
+    priority: Priority,
+    queued_at: Instant,
+    /// 順序のキー（大きいほど先に実行する）
+    key: i128,
+    seq: u64,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    /// キーが大きいほど、キーが同じなら先に追加されたほど大きい
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl Queue {
+    fn new() -> Self {
+        Queue {
+            entries: BinaryHeap::new(),
+            aging: None,
+            epoch: Instant::now(),
+            next_seq: 0,
+            changed: Arc::new(AtomicBool::new(false)),
+            closed: false,
+        }
+    }
+
+    /// 順序のキーを求めます。
+    ///
+    /// 時刻 `t` における実効優先度 `priority + (t - queued_at) / interval` に `interval` を掛けると
+    /// `priority * interval - (queued_at - epoch) + (t - epoch)` となり、最後の項はすべてのタスクで共通なので除きます。
+    fn key(&self, priority: i32, queued_at: Instant) -> i128 {
+        match self.aging {
+            Some(interval) if !interval.is_zero() => {
+                let waited_since =
+                    queued_at.saturating_duration_since(self.epoch).as_nanos() as i128;
+                i128::from(priority) * interval.as_nanos() as i128 - waited_since
+            }
+            _ => i128::from(priority),
+        }
+    }
+
+    fn push(&mut self, task: Task, priority: Priority) {
+        let queued_at = Instant::now();
+        let key = self.key(priority.get(), queued_at);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        priority.enqueue(&self.changed);
+        self.entries.push(Entry {
+            task,
+            priority,
+            queued_at,
+            key,
+            seq,
+        });
+    }
+
+    /// 実効優先度が最も高い（同じ場合は先に追加された）タスクを取り出します。
+    fn pop(&mut self) -> Option<Task> {
+        if self.changed.swap(false, Ordering::SeqCst) {
+            self.rekey();
+        }
+        let entry = self.entries.pop()?;
+        entry.priority.dequeue(&self.changed);
+        Some(entry.task)
+    }
+
+    /// 現在の優先度とエージングの設定で、すべてのタスクのキーを計算し直します。
+    fn rekey(&mut self) {
+        let mut entries = std::mem::take(&mut self.entries).into_vec();
+        for entry in &mut entries {
+            entry.key = self.key(entry.priority.get(), entry.queued_at);
+        }
+        self.entries = BinaryHeap::from(entries);
+    }
+}
+
+impl ThreadPool {
+    /// `size` 個のワーカースレッドを持つスレッドプールを作成します。
+    ///
+    /// `size` が0の場合は1として扱います。
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue::new()),
+            available: Condvar::new(),
+        });
+        for index in 0..size {
+            let shared = Arc::clone(&shared);
+            thread::Builder::new()
+                .name(format!("parallel_world-worker-{index}"))
+                .spawn(move || work(&shared))
+                .expect("failed to spawn worker thread");
+        }
+        ThreadPool { shared, size }
+    }
+
+    /// キューで `interval` 待つごとに、タスクの優先度を1ずつ上げるようにします。
+    ///
+    /// 優先度は待機時間に比例して連続的に上がるものとして比較します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, ThreadPool, World};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let mv = Multiverse::with_executor(ThreadPool::new(1).with_aging(Duration::from_millis(10)));
+    /// let order = Arc::new(Mutex::new(Vec::new()));
+    /// mv.add("busy".to_string(), World::from(|| sleep(Duration::from_millis(150)))).unwrap();
+    /// for (id, priority) in [("old", 0), ("new", 3)] {
+    ///     let order = Arc::clone(&order);
+    ///     let world = World::from(move || order.lock().unwrap().push(id)).with_priority(priority);
+    ///     mv.add(id.to_string(), world).unwrap();
+    /// }
+    ///
+    /// mv.exec("busy").unwrap();
+    /// mv.exec("old").unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// mv.exec("new").unwrap();
+    ///
+    /// // 長く待った "old" の優先度は、後から来た "new" を上回っている
+    /// mv.wait_all(&["busy", "old", "new"]).unwrap();
+    /// assert_eq!(*order.lock().unwrap(), ["old", "new"]);
+    /// ```
+    pub fn with_aging(self, interval: Duration) -> Self {
+        let mut queue = self.shared.queue.lock().unwrap();
+        queue.aging = Some(interval);
+        queue.rekey();
+        drop(queue);
+        self
+    }
+
+    /// ワーカースレッドの数を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::ThreadPool;
+    ///
+    /// assert_eq!(ThreadPool::new(4).size(), 4);
+    /// assert_eq!(ThreadPool::new(0).size(), 1);
+    /// ```
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// ワーカースレッドの本体です。キューからタスクを取り出して実行し続けます。
+fn work(shared: &Shared) {
+    loop {
+        let task = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                if let Some(task) = queue.pop() {
+                    break task;
+                }
+                if queue.closed {
+                    return; // ThreadPoolが破棄され、キューも空になった
+                }
+                queue = shared.available.wait(queue).unwrap();
+            }
+        };
+        // タスクがパニックしてもワーカーは失わない
+        let _ = panic::catch_unwind(AssertUnwindSafe(task));
+    }
+}
+
+impl Executor for ThreadPool {
+    fn execute(&self, task: Task) {
+        self.execute_with_priority(task, Priority::default());
+    }
+
+    fn execute_with_priority(&self, task: Task, priority: Priority) {
+        self.shared.queue.lock().unwrap().push(task, priority);
+        self.shared.available.notify_one();
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().closed = true;
+        self.shared.available.notify_all();
+    }
+}
+
+/// 実行器が設定されていないFuture（`World::from_future`）を実行する、組み込みの実行器を返します。
+///
+/// すべてのFutureを1つのワーカースレッドでポーリングします。
+pub(crate) fn builtin() -> Arc<dyn Executor> {
+    static BUILTIN: OnceLock<Arc<ThreadPool>> = OnceLock::new();
+    BUILTIN.get_or_init(|| Arc::new(ThreadPool::new(1))).clone()
+}
+
+/// 実行器の上でポーリングされるFuture
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// `future` を `executor` で実行します。
+///
+/// 起こされる（`Waker::wake`）たびに、一度だけポーリングするタスクを `executor` に渡します。
+pub(crate) fn spawn_future(executor: Arc<dyn Executor>, priority: Priority, future: BoxFuture) {
+    let task = Arc::new(FutureTask {
+        future: Mutex::new(Some(future)),
+        scheduled: AtomicBool::new(false),
+        executor,
+        priority,
+    });
+    task.schedule();
+}
+
+/// 実行器の上で実行されるFutureと、そのWakerの状態
+struct FutureTask {
+    /// 完了するまでのFuture（完了後は`None`）
+    future: Mutex<Option<BoxFuture>>,
+    /// ポーリングするタスクが実行器に渡されていて、まだ実行されていないかどうか
+    scheduled: AtomicBool,
+    executor: Arc<dyn Executor>,
+    priority: Priority,
+}
+
+impl FutureTask {
+    /// まだ渡していなければ、ポーリングするタスクを実行器に渡します。
+    fn schedule(self: &Arc<Self>) {
+        if self.scheduled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let task = Arc::clone(self);
+        self.executor
+            .execute_with_priority(Box::new(move || task.poll()), self.priority.clone());
+    }
+
+    /// Futureを一度ポーリングし、完了していれば破棄します。
+    fn poll(self: Arc<Self>) {
+        // ポーリング中に起こされた場合は、もう一度ポーリングする
+        self.scheduled.store(false, Ordering::SeqCst);
+        let mut slot = self.future.lock().unwrap();
+        let Some(future) = slot.as_mut() else {
+            return;
+        };
+        let waker = Waker::from(Arc::clone(&self));
+        if future
+            .as_mut()
+            .poll(&mut Context::from_waker(&waker))
+            .is_ready()
+        {
+            *slot = None;
+        }
+    }
+}
+
+impl Wake for FutureTask {
+    fn wake(self: Arc<Self>) {
+        self.schedule();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.schedule();
+    }
+}
@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Once};
+
+/// # Failure
+///
+/// `WorldStatus::Failed` が保持する、失敗の詳細情報です。
+///
+/// パニックによって失敗した場合、パニックのメッセージ（`&str` / `String` のペイロード）、
+/// パニックが発生した場所、およびバックトレース（`RUST_BACKTRACE` などで有効な場合）が記録されます。
+/// `World::try_from` のクロージャが `Err(e)` を返して失敗した場合は、`e` がそのまま保持され、
+/// `downcast_error` で元の型として取り出せます。
+#[derive(Clone)]
+pub struct Failure {
+    /// 失敗の理由を表すメッセージ
+    pub message: String,
+    /// パニックが発生したソースコード上の位置（`ファイル:行:列`）
+    pub location: Option<String>,
+    /// パニック発生時に取得されたバックトレース
+    pub backtrace: Option<String>,
+    /// クロージャが返したエラー（パニックによる失敗の場合は`None`）
+    error: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl Failure {
+    /// メッセージだけを持つ `Failure` を作成します。
+    pub fn new(message: impl Into<String>) -> Self {
+        Failure {
+            message: message.into(),
+            location: None,
+            backtrace: None,
+            error: None,
+        }
+    }
+
+    /// クロージャが返したエラーから `Failure` を作成します。メッセージはエラーの `Display` 表現になります。
+    pub fn from_error<E>(error: E) -> Self
+    where
+        E: fmt::Display + Send + Sync + 'static,
+    {
+        let message = error.to_string();
+        Failure {
+            error: Some(Arc::new(error)),
+            ..Failure::new(message)
+        }
+    }
+
+    /// クロージャが返したエラーによる失敗かどうかを返します（`false` ならパニックによる失敗です）。
+    pub fn is_error(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// クロージャが返したエラーを、元の型 `E` として参照します。
+    ///
+    /// パニックによる失敗の場合や、型が一致しない場合は`None`を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::Failure;
+    ///
+    /// let failure = Failure::from_error(std::fmt::Error);
+    /// assert!(failure.downcast_error::<std::fmt::Error>().is_some());
+    /// assert!(failure.downcast_error::<String>().is_none());
+    /// ```
+    pub fn downcast_error<E: 'static>(&self) -> Option<&E> {
+        self.error.as_deref()?.downcast_ref::<E>()
+    }
+}
+
+impl fmt::Debug for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Failure")
+            .field("message", &self.message)
+            .field("location", &self.location)
+            .field("backtrace", &self.backtrace)
+            .field("is_error", &self.is_error())
+            .finish()
+    }
+}
+
+impl PartialEq for Failure {
+    /// メッセージ・発生位置・バックトレースが等しく、保持しているエラーが同一のものであれば等しいとみなします。
+    fn eq(&self, other: &Self) -> bool {
+        let same_error = match (&self.error, &other.error) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        self.message == other.message
+            && self.location == other.location
+            && self.backtrace == other.backtrace
+            && same_error
+    }
+}
+
+impl From<&str> for Failure {
+    fn from(message: &str) -> Self {
+        Failure::new(message)
+    }
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::new(message)
+    }
+}
+
+impl fmt::Display for Failure {
+    /// `Failure` をメッセージと（分かれば）発生位置の形式でフォーマットします。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::Failure;
+    ///
+    /// assert_eq!(Failure::new("boom").to_string(), "boom");
+    ///
+    /// let mut failure = Failure::new("boom");
+    /// failure.location = Some("src/main.rs:10:5".to_string());
+    /// assert_eq!(failure.to_string(), "boom (at src/main.rs:10:5)");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} (at {})", self.message, location),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// パニックペイロードからメッセージを取り出します。
+///
+/// `panic!` に渡された `&str` / `String` であればその内容を、それ以外の型であれば
+/// 型が不明であることを示す文字列を返します。
+///
+/// # 例
+/// ```
+/// use parallel_world::failure::payload_message;
+///
+/// let payload = std::panic::catch_unwind(|| panic!("value was {}", 42)).unwrap_err();
+/// assert_eq!(payload_message(payload.as_ref()), "value was 42");
+/// ```
+pub fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any> (non-string panic payload)".to_string()
+    }
+}
+
+thread_local! {
+    /// 現在のスレッドで `catch_panic` が実行中かどうか
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    /// パニックフックが記録した、直近のパニックの発生位置とバックトレース
+    static LAST_PANIC: RefCell<Option<(Option<String>, Option<String>)>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// パニックの発生位置とバックトレースを記録するパニックフックを一度だけ登録します。
+///
+/// 既存のフックは引き続き呼び出されるため、標準エラー出力へのパニックメッセージの表示などは変わりません。
+fn install_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CAPTURING.with(Cell::get) {
+                let location = info.location().map(|l| l.to_string());
+                let backtrace = Backtrace::capture();
+                let backtrace = (backtrace.status() == BacktraceStatus::Captured)
+                    .then(|| backtrace.to_string());
+                LAST_PANIC.with(|last| *last.borrow_mut() = Some((location, backtrace)));
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// `f` を実行し、パニックが発生した場合は `Failure` と元のペイロードを返します。
+pub(crate) fn catch_panic<R, F>(f: F) -> Result<R, (Failure, Box<dyn Any + Send>)>
+where
+    F: FnOnce() -> R,
+{
+    install_hook();
+    let was_capturing = CAPTURING.with(|c| c.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CAPTURING.with(|c| c.set(was_capturing));
+
+    result.map_err(|payload| {
+        let (location, backtrace) = LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_default();
+        let failure = Failure {
+            message: payload_message(payload.as_ref()),
+            location,
+            backtrace,
+            error: None,
+        };
+        (failure, payload)
+    })
+}
+
+/// `catch_panic` で捕捉したパニックを再開します。
+///
+/// `resume_unwind` はパニックフックを呼び出さないため、`failure` に記録された発生位置とバックトレースを
+/// 引き継いでから再開します。外側の `catch_panic` は元のパニックと同じ `Failure` を得られます。
+pub(crate) fn resume_panic(failure: Failure, payload: Box<dyn Any + Send>) -> ! {
+    LAST_PANIC.with(|last| *last.borrow_mut() = Some((failure.location, failure.backtrace)));
+    panic::resume_unwind(payload)
+}
@@ -1,6 +1,34 @@
+pub mod cancel;
+pub mod dependency;
+pub mod error;
+pub mod event;
+pub mod executor;
+pub mod failure;
+pub mod logs;
+pub mod mailbox;
+pub mod metrics;
+#[cfg(feature = "openmetrics")]
+pub mod openmetrics;
 pub mod parallel_worlds;
+pub mod process;
+pub mod report;
+pub mod retry;
+pub mod stream;
+pub mod supervisor;
 pub mod world;
 
 // クレートのトップレベルで利用できるように、use宣言を追加
-pub use parallel_worlds::Multiverse;
-pub use world::{AnyWorld, World, WorldStatus}; // AnyWorldを追加
\ No newline at end of file
+pub use cancel::CancelToken;
+pub use dependency::DependencyPolicy;
+pub use error::WorldError;
+pub use event::WorldEvent;
+pub use executor::{Executor, ThreadPool};
+pub use failure::Failure;
+pub use logs::{LogLine, LogStream, Logger};
+pub use metrics::{MultiverseMetrics, WorldMetrics};
+pub use parallel_worlds::{Multiverse, WorldResult};
+pub use report::{Report, Reporter};
+pub use retry::{Backoff, RetryPolicy};
+pub use stream::{Emitter, Stream, StreamWorld};
+pub use supervisor::{RestartStrategy, Supervisor};
+pub use world::{AnyWorld, World, WorldStatus}; // AnyWorldを追加
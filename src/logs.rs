@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Instant;
+
+/// Worldごとに保持するログの既定の最大行数
+pub(crate) const DEFAULT_LOG_CAPACITY: usize = 1000;
+
+/// # LogStream
+///
+/// ログの行の出力元です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStream {
+    /// `Logger` で記録された行
+    Logger,
+    /// 子プロセスの標準出力（`World::in_process` のみ）
+    Stdout,
+    /// 子プロセスの標準エラー出力（`World::in_process` のみ）
+    Stderr,
+}
+
+/// # LogLine
+///
+/// Worldが出力したログの1行です（`World::logs` / `Multiverse::logs` で取得できます）。
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    /// 出力元
+    pub stream: LogStream,
+    /// 行の内容（改行は含みません）
+    pub text: String,
+    /// 記録された時刻
+    pub at: Instant,
+}
+
+/// Worldのログを保持するリングバッファ
+///
+/// 最大行数を超えると、古い行から捨てられます。
+#[derive(Debug)]
+pub(crate) struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+    /// 新しい行を受け取る購読者（`follow` で追加されたもの）
+    followers: Vec<mpsc::Sender<LogLine>>,
+}
+
+pub(crate) type SharedLogBuffer = Arc<Mutex<LogBuffer>>;
+
+impl LogBuffer {
+    pub(crate) fn new(capacity: usize) -> Self {
+        LogBuffer {
+            lines: VecDeque::new(),
+            capacity,
+            followers: Vec::new(),
+        }
+    }
+
+    /// 1行を記録し、購読者に送ります。
+    pub(crate) fn push(&mut self, stream: LogStream, text: String) {
+        let line = LogLine {
+            stream,
+            text,
+            at: Instant::now(),
+        };
+        self.followers
+            .retain(|follower| follower.send(line.clone()).is_ok());
+        if self.capacity == 0 {
+            return;
+        }
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// 保持している行を古い順に返します。
+    pub(crate) fn lines(&self) -> Vec<LogLine> {
+        self.lines.iter().cloned().collect()
+    }
+
+    /// 最大行数を変更します。超えた分は古い行から捨てられます。
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.lines.len() > capacity {
+            self.lines.pop_front();
+        }
+    }
+
+    /// 保持している行を送り終えた後、新しい行を受け取り続けるレシーバーを返します。
+    pub(crate) fn follow(&mut self) -> mpsc::Receiver<LogLine> {
+        let (tx, rx) = mpsc::channel();
+        for line in &self.lines {
+            let _ = tx.send(line.clone());
+        }
+        self.followers.push(tx);
+        rx
+    }
+
+    /// すべての購読者への送信を終了します（レシーバーの反復が終わります）。
+    pub(crate) fn close_followers(&mut self) {
+        self.followers.clear();
+    }
+}
+
+/// # Logger
+///
+/// `World::with_logger` で作成されたWorldのクロージャが受け取る、ログを記録するためのハンドルです。
+///
+/// 記録した行はWorldごとのリングバッファに保持され、`World::logs` / `Multiverse::logs` で後から取得したり、
+/// `World::follow_logs` / `Multiverse::follow_logs` で記録されるそばから受け取ったりできます。
+///
+/// `std::io::Write` を実装しているため、`writeln!` でも記録できます。
+/// この場合、改行までの内容が1行として記録されます（改行で終わらない内容は `flush` か破棄のときに記録されます）。
+/// `Clone` で複製でき、複製はすべて同じバッファに記録します。
+#[derive(Debug)]
+pub struct Logger {
+    buffer: SharedLogBuffer,
+    /// `Write` で書き込まれた、まだ改行が来ていない内容
+    partial: Vec<u8>,
+}
+
+impl Logger {
+    pub(crate) fn new(buffer: SharedLogBuffer) -> Self {
+        Logger {
+            buffer,
+            partial: Vec::new(),
+        }
+    }
+
+    /// ログを記録します。`line` が改行を含む場合は、複数の行として記録されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::logs::Logger;
+    ///
+    /// let logger = Logger::default();
+    /// logger.log("connecting");
+    /// logger.log("retrying\nconnected");
+    ///
+    /// let lines: Vec<_> = logger.lines().into_iter().map(|line| line.text).collect();
+    /// assert_eq!(lines, ["connecting", "retrying", "connected"]);
+    /// ```
+    pub fn log(&self, line: impl AsRef<str>) {
+        let text = line.as_ref();
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let mut buffer = self.buffer.lock().unwrap();
+        for line in text.split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            buffer.push(LogStream::Logger, line.to_string());
+        }
+    }
+
+    /// これまでに記録され、バッファに残っている行を取得します。
+    pub fn lines(&self) -> Vec<LogLine> {
+        self.buffer.lock().unwrap().lines()
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new(Arc::new(Mutex::new(LogBuffer::new(DEFAULT_LOG_CAPACITY))))
+    }
+}
+
+impl Clone for Logger {
+    fn clone(&self) -> Self {
+        Self::new(Arc::clone(&self.buffer))
+    }
+}
+
+impl io::Write for Logger {
+    /// # 例
+    /// ```
+    /// use parallel_world::logs::Logger;
+    /// use std::io::Write;
+    ///
+    /// let mut logger = Logger::default();
+    /// write!(logger, "processed {} ", 42).unwrap();
+    /// writeln!(logger, "items").unwrap();
+    /// write!(logger, "no newline").unwrap();
+    /// assert_eq!(logger.lines().len(), 1);
+    ///
+    /// logger.flush().unwrap();
+    /// let lines: Vec<_> = logger.lines().into_iter().map(|line| line.text).collect();
+    /// assert_eq!(lines, ["processed 42 items", "no newline"]);
+    /// ```
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.partial.extend_from_slice(buf);
+        if let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') {
+            let complete: Vec<u8> = self.partial.drain(..=end).collect();
+            self.log(String::from_utf8_lossy(&complete));
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.partial.is_empty() {
+            let rest = std::mem::take(&mut self.partial);
+            self.log(String::from_utf8_lossy(&rest));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Logger {
+    fn drop(&mut self) {
+        let _ = io::Write::flush(self);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::error::WorldError;
+use crate::world::World;
+use std::any::{Any, TypeId};
+use std::sync::{Arc, Mutex, mpsc};
+
+/// 型を消したメッセージを受信箱に送る関数
+type SendFn = Box<dyn Fn(Box<dyn Any + Send>) -> Result<(), WorldError> + Send + Sync>;
+
+/// Worldの受信箱にメッセージを送るための、型を消した送信側
+pub(crate) struct InboxSlot {
+    /// 受信箱が閉じられると `None` になります。
+    send: Mutex<Option<SendFn>>,
+    message_type: TypeId,
+    message_type_name: &'static str,
+}
+
+impl InboxSlot {
+    /// メッセージの型を確認して、受信箱に送ります。
+    pub(crate) fn send(
+        &self,
+        message: Box<dyn Any + Send>,
+        message_type_name: &'static str,
+    ) -> Result<(), WorldError> {
+        if (*message).type_id() != self.message_type {
+            return Err(WorldError::DowncastFailed {
+                expected: message_type_name,
+                actual: self.message_type_name,
+            });
+        }
+        let send = self.send.lock().unwrap();
+        let send = send.as_ref().ok_or(WorldError::InboxClosed)?;
+        send(message)
+    }
+
+    /// 受信箱を閉じます。送信側が破棄されるため、受信側の `recv` は残りのメッセージを受け取った後にエラーを返します。
+    fn close(&self) {
+        self.send.lock().unwrap().take();
+    }
+}
+
+impl<R: Send + 'static> World<R> {
+    /// 型 `M` のメッセージを受け取る受信箱を持つWorldを作成します。
+    ///
+    /// クロージャには受信箱の `mpsc::Receiver<M>` が渡されます。
+    /// メッセージは `send()` や `Multiverse::send` で、Worldの実行前からでも送ることができます。
+    ///
+    /// Worldが停止されるか完了すると受信箱は閉じられ、`recv` は残りのメッセージを受け取った後にエラーを返します。
+    /// そのため、`for message in inbox` のように受け取り続けるクロージャも、停止されると終了します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::sync::mpsc::Receiver;
+    ///
+    /// let world = World::with_inbox(|inbox: Receiver<i32>| {
+    ///     let mut total = 0;
+    ///     for n in inbox {
+    ///         if n == 0 {
+    ///             break;
+    ///         }
+    ///         total += n;
+    ///     }
+    ///     total
+    /// });
+    ///
+    /// world.send(1).unwrap();
+    /// world.send(2).unwrap();
+    /// world.start().unwrap();
+    /// world.send(3).unwrap();
+    /// world.send(0).unwrap();
+    /// assert_eq!(world.status(), Ok(6));
+    /// ```
+    pub fn with_inbox<M, F>(f: F) -> Self
+    where
+        M: Send + 'static,
+        F: FnOnce(mpsc::Receiver<M>) -> R + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel::<M>();
+        let send: SendFn = Box::new(move |message| {
+            let message = message.downcast::<M>().expect("message type was checked");
+            tx.send(*message).map_err(|_| WorldError::InboxClosed)
+        });
+        let slot = Arc::new(InboxSlot {
+            send: Mutex::new(Some(send)),
+            message_type: TypeId::of::<M>(),
+            message_type_name: std::any::type_name::<M>(),
+        });
+
+        let cancelled = Arc::clone(&slot);
+        let mut world = World::with_job(Some(Box::new(move |ctx| {
+            // 停止されたら、受信箱で待っているクロージャを起こす
+            ctx.cancel_token.on_cancel(move || cancelled.close());
+            Ok(f(rx))
+        })));
+
+        // 完了したら（実行されずに停止された場合も）、受信箱を閉じる
+        let completed = Arc::clone(&slot);
+        world.on_complete(move || completed.close());
+        world.inbox = Some(slot);
+        world
+    }
+
+    /// `with_inbox` で作成されたWorldの受信箱にメッセージを送ります。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NoInbox)` - `with_inbox` で作成されたWorldではない場合に返されます。
+    /// * `Err(WorldError::DowncastFailed)` - `M` が受信箱のメッセージの型と異なる場合に返されます。
+    /// * `Err(WorldError::InboxClosed)` - Worldが停止・完了しているか、クロージャが受信箱を破棄した場合に返されます。
+    pub fn send<M: Send + 'static>(&self, message: M) -> Result<(), WorldError> {
+        self.send_any(Box::new(message), std::any::type_name::<M>())
+    }
+
+    /// 型を消したメッセージを受信箱に送ります。
+    pub(crate) fn send_any(
+        &self,
+        message: Box<dyn Any + Send>,
+        message_type_name: &'static str,
+    ) -> Result<(), WorldError> {
+        self.inbox
+            .as_ref()
+            .ok_or(WorldError::NoInbox)?
+            .send(message, message_type_name)
+    }
+}
@@ -1,5 +1,4 @@
 use parallel_world::{Multiverse, World};
-use std::sync::{Arc, Mutex};
 use std::thread::sleep;
 use std::time::Duration;
 
@@ -106,20 +105,17 @@ fn main() {
     }
     println!("World E の最終状態: {}", pw.progress("World_E").unwrap());
 
-    // World D: 協調的に停止できるタスク（再利用）
-    let stop_flag_d = Arc::new(Mutex::new(false));
-    let stop_flag_d_clone = Arc::clone(&stop_flag_d);
-
-    let world_d = World::from(move || {
+    // World D: 協調的に停止できるタスク（キャンセルトークンを受け取る）
+    let world_d = World::with_cancel(|token| {
         println!("[World D] 開始: 20ms間隔で処理します (停止可能)。");
         let mut i = 0;
         loop {
-            if *stop_flag_d_clone.lock().unwrap() {
+            if token.is_cancelled() {
                 println!("[World D] 停止シグナルを受信しました。終了します。");
                 break i; // 停止した時点のiを返す
             }
             println!("[World D] 処理中... {}", i);
-            sleep(Duration::from_millis(20));
+            token.sleep_or_cancel(Duration::from_millis(20));
             i += 1;
             if i > 50 {
                 println!("[World D] 処理回数の上限に達しました。自然に完了します。");
@@ -137,7 +133,7 @@ fn main() {
     sleep(Duration::from_millis(50)); // 少し実行させる
 
     println!("World D を停止シグナルで停止します。");
-    *stop_flag_d.lock().unwrap() = true; // 停止フラグを設定
+    pw.kill("World_D").expect("Failed to kill World_D"); // キャンセルトークンをキャンセル
 
     println!("World D の完了を待機します。");
     match pw.status::<i32>("World_D") {
@@ -151,4 +147,4 @@ fn main() {
     println!("World D の最終状態: {}", pw.progress("World_D").unwrap());
 
     println!("\n--- Parallel World Application End ---");
-}
\ No newline at end of file
+}
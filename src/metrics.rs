@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// # WorldMetrics
+///
+/// Worldの実行にかかった時間の記録です（`World::metrics` で取得できます）。
+///
+/// `created_at` 以外は現在の実行についての値で、`reset()` すると記録し直されます。
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldMetrics {
+    /// Worldが作成された時刻
+    pub created_at: Instant,
+    /// 実行器のキューに入った時刻（実行器で実行される場合のみ）
+    pub queued_at: Option<Instant>,
+    /// 実行が始まった時刻
+    pub started_at: Option<Instant>,
+    /// 実行が終わった時刻
+    pub finished_at: Option<Instant>,
+    /// 実行したスレッドが消費したCPU時間（Linuxでのみ取得できます）
+    ///
+    /// 子プロセス（`World::in_process`）が消費したCPU時間は含まれません。
+    pub cpu_time: Option<Duration>,
+}
+
+impl WorldMetrics {
+    /// 実行にかかった時間（実時間）を返します。実行が終わっていなければ `None` です。
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.finished_at?.duration_since(self.started_at?))
+    }
+
+    /// 実行器のキューで待っていた時間を返します。
+    pub fn queue_time(&self) -> Option<Duration> {
+        Some(self.started_at?.duration_since(self.queued_at?))
+    }
+}
+
+/// # MultiverseMetrics
+///
+/// `Multiverse` に登録されたWorldの実行時間を集計したものです（`Multiverse::metrics` で取得できます）。
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MultiverseMetrics {
+    /// 状態ごとのWorldの数（キーは `WorldStatus::name`）
+    pub counts: HashMap<&'static str, usize>,
+    /// 実行が終わったWorldの実行時間の中央値
+    pub p50: Option<Duration>,
+    /// 実行が終わったWorldの実行時間の95パーセンタイル
+    pub p95: Option<Duration>,
+    /// Worldごとの記録
+    pub worlds: HashMap<String, WorldMetrics>,
+}
+
+impl MultiverseMetrics {
+    /// Worldごとの状態と記録から集計します。
+    pub(crate) fn aggregate(worlds: Vec<(String, &'static str, WorldMetrics)>) -> Self {
+        let mut metrics = MultiverseMetrics::default();
+        let mut durations = Vec::new();
+        for (id, status, world_metrics) in worlds {
+            *metrics.counts.entry(status).or_default() += 1;
+            durations.extend(world_metrics.duration());
+            metrics.worlds.insert(id, world_metrics);
+        }
+        durations.sort();
+        metrics.p50 = percentile(&durations, 50);
+        metrics.p95 = percentile(&durations, 95);
+        metrics
+    }
+}
+
+/// 昇順に並んだ `sorted` の `p` パーセンタイルを、最近接順位法で求めます。
+fn percentile(sorted: &[Duration], p: usize) -> Option<Duration> {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+/// 1回の実行の間に記録される時刻とCPU時間
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Timings {
+    pub(crate) queued_at: Option<Instant>,
+    pub(crate) started_at: Option<Instant>,
+    pub(crate) finished_at: Option<Instant>,
+    pub(crate) cpu_time: Option<Duration>,
+}
+
+impl Timings {
+    /// `before`（`thread_cpu_time` で取得した値）から現在までに消費したCPU時間を加算します。
+    pub(crate) fn add_cpu_time_since(&mut self, before: Option<Duration>) {
+        if let (Some(before), Some(after)) = (before, thread_cpu_time()) {
+            let total = self.cpu_time.unwrap_or_default();
+            self.cpu_time = Some(total + after.saturating_sub(before));
+        }
+    }
+}
+
+/// 現在のスレッドが消費したCPU時間を返します。取得できない環境では `None` を返します。
+///
+/// Futureのポーリングごとに呼ばれるため、ファイルを読まずに `clock_gettime` で取得します。
+#[cfg(target_os = "linux")]
+pub(crate) fn thread_cpu_time() -> Option<Duration> {
+    use std::ffi::{c_int, c_long};
+
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 3;
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: c_long,
+        tv_nsec: c_long,
+    }
+    unsafe extern "C" {
+        fn clock_gettime(clock_id: c_int, tp: *mut Timespec) -> c_int;
+    }
+    let mut ts = Timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // ts は有効な書き込み先で、呼び出しの間だけ借用される
+    if unsafe { clock_gettime(CLOCK_THREAD_CPUTIME_ID, &mut ts) } != 0 {
+        return None;
+    }
+    Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+/// 現在のスレッドが消費したCPU時間を返します。取得できない環境では `None` を返します。
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn thread_cpu_time() -> Option<Duration> {
+    None
+}
@@ -0,0 +1,412 @@
+use crate::event::WorldEvent;
+use crate::parallel_worlds::Multiverse;
+use crate::world::WorldStatus;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// 出力するメトリクス名の接頭辞
+const PREFIX: &str = "parallel_world";
+
+/// HTTPの応答の `Content-Type`
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// 1つの接続でリクエストの読み込みや応答の書き込みを待つ最大時間
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 実行時間のヒストグラムの既定のバケットの上限（秒）
+const DEFAULT_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Worldの数を出力する状態（`WorldStatus::name`）。Worldが1つもない状態も0として出力します。
+const STATUSES: [&str; 11] = [
+    "Ready", "Blocked", "Queued", "Running", "Retrying", "Finished", "Failed", "Stopped",
+    "Skipped", "TimedOut", "Killed",
+];
+
+/// # Exporter
+///
+/// `Multiverse` の状態を OpenMetrics（Prometheus）のテキスト形式で出力するエクスポーターです。
+/// `openmetrics` フィーチャーを有効にすると利用できます。
+///
+/// 次のメトリクスを出力します。
+///
+/// - `parallel_world_worlds{status="..."}`（gauge）: 状態ごとのWorldの数
+/// - `parallel_world_worlds_started_total` など（counter）: 実行を開始・正常終了・失敗・停止したWorldの数
+///   （停止には `Stopped` のほか `Killed` と `TimedOut` を含みます）
+/// - `parallel_world_world_duration_seconds`（histogram）: 実行を開始してから終了するまでの時間
+///
+/// カウンターとヒストグラムは状態が変化するたびに集計するため、エクスポーターを作成する前の実行は含まれません。
+/// 停止には、実行中・依存待ち・実行待ちの状態から終了した場合だけを数えます（停止後の強制終了は数えません）。
+///
+/// `render` で文字列として取得するか、`serve` で HTTP のエンドポイントとして公開できます。
+/// 複製したエクスポーターは、同じ集計を共有します。
+///
+/// # 例
+/// ```
+/// use parallel_world::openmetrics::Exporter;
+/// use parallel_world::{Multiverse, World};
+/// use std::sync::Arc;
+///
+/// let mv = Arc::new(Multiverse::new());
+/// let exporter = Exporter::new(Arc::clone(&mv));
+///
+/// mv.add("ok".to_string(), World::from(|| 1)).unwrap();
+/// mv.add("broken".to_string(), World::from(|| -> i32 { panic!("boom") })).unwrap();
+/// mv.start_all();
+/// mv.wait_all(&["ok", "broken"]).unwrap();
+///
+/// let text = exporter.render();
+/// assert!(text.contains("parallel_world_worlds{status=\"Finished\"} 1\n"));
+/// assert!(text.contains("parallel_world_worlds{status=\"Running\"} 0\n"));
+/// assert!(text.contains("parallel_world_worlds_started_total 2\n"));
+/// assert!(text.contains("parallel_world_worlds_failed_total 1\n"));
+/// assert!(text.contains("parallel_world_world_duration_seconds_count 2\n"));
+/// assert!(text.ends_with("# EOF\n"));
+/// ```
+#[derive(Clone)]
+pub struct Exporter {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    multiverse: Arc<Multiverse>,
+    /// `Multiverse` から状態の変化が通知されるたびに更新される（購読側は弱い参照で保持する）
+    counters: Arc<Mutex<Counters>>,
+}
+
+/// 状態の変化から数えた値
+struct Counters {
+    started: u64,
+    finished: u64,
+    failed: u64,
+    stopped: u64,
+    /// 実行中のWorldの、実行を開始した時刻
+    running_since: HashMap<String, Instant>,
+    durations: Histogram,
+}
+
+/// 実行時間のヒストグラム
+struct Histogram {
+    /// バケットの上限（秒）と、その上限以下の観測値の数
+    buckets: Vec<(f64, u64)>,
+    sum: f64,
+    count: u64,
+}
+
+impl Exporter {
+    /// `multiverse` の状態を出力するエクスポーターを作成します。
+    pub fn new(multiverse: Arc<Multiverse>) -> Self {
+        Self::with_buckets(multiverse, &DEFAULT_BUCKETS)
+    }
+
+    /// 実行時間のヒストグラムのバケットの上限（秒）を指定して、エクスポーターを作成します。
+    ///
+    /// `+Inf` のバケットは自動的に追加されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::openmetrics::Exporter;
+    /// use parallel_world::{Multiverse, World};
+    /// use std::sync::Arc;
+    ///
+    /// let mv = Arc::new(Multiverse::new());
+    /// let exporter = Exporter::with_buckets(Arc::clone(&mv), &[60.0, 3600.0]);
+    /// mv.add("quick".to_string(), World::from(|| ())).unwrap();
+    /// mv.exec("quick").unwrap();
+    /// mv.status::<()>("quick").unwrap();
+    ///
+    /// let text = exporter.render();
+    /// assert!(text.contains("parallel_world_world_duration_seconds_bucket{le=\"60.0\"} 1\n"));
+    /// assert!(text.contains("parallel_world_world_duration_seconds_bucket{le=\"+Inf\"} 1\n"));
+    /// ```
+    pub fn with_buckets(multiverse: Arc<Multiverse>, buckets: &[f64]) -> Self {
+        let mut bounds = buckets.to_vec();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        let counters = Arc::new(Mutex::new(Counters {
+            started: 0,
+            finished: 0,
+            failed: 0,
+            stopped: 0,
+            running_since: HashMap::new(),
+            durations: Histogram {
+                buckets: bounds.into_iter().map(|bound| (bound, 0)).collect(),
+                sum: 0.0,
+                count: 0,
+            },
+        }));
+        // エクスポーターが破棄されたら購読を解除する
+        let recorded = Arc::downgrade(&counters);
+        multiverse.on_event(Box::new(move |event| match recorded.upgrade() {
+            Some(counters) => {
+                counters.lock().unwrap().record(event);
+                true
+            }
+            None => false,
+        }));
+        Exporter {
+            inner: Arc::new(Inner {
+                multiverse,
+                counters,
+            }),
+        }
+    }
+
+    /// 現在の状態を OpenMetrics のテキスト形式で返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::openmetrics::Exporter;
+    /// use parallel_world::{Multiverse, World, WorldStatus};
+    /// use std::process::Command;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let mv = Arc::new(Multiverse::new());
+    /// let exporter = Exporter::new(Arc::clone(&mv));
+    /// let mut cmd = Command::new("sh");
+    /// cmd.args(["-c", "trap '' TERM; sleep 5"]); // SIGTERMを無視する
+    /// mv.add("stubborn".to_string(), World::in_process(cmd).with_kill_grace(Duration::from_millis(10)))
+    ///     .unwrap();
+    /// mv.exec("stubborn").unwrap();
+    ///
+    /// // 停止してから強制終了しても、停止は1回だけ数える
+    /// mv.stop_all();
+    /// mv.kill("stubborn").unwrap();
+    /// assert_eq!(mv.progress("stubborn").unwrap(), WorldStatus::Killed);
+    /// assert!(exporter.render().contains("parallel_world_worlds_stopped_total 1\n"));
+    /// ```
+    pub fn render(&self) -> String {
+        // 集計はWorldの状態をロックしたまま更新されるため、状態を参照し終えてから集計をロックする
+        let counts = self.inner.multiverse.metrics().counts;
+        let counters = self.inner.counters.lock().unwrap();
+
+        let mut out = String::new();
+        // String への書き込みは失敗しない
+        let _ = write_metrics(&mut out, &counts, &counters);
+        out
+    }
+
+    /// `addr` で HTTP の接続を受け付け、`GET /metrics` に `render` の結果を返すサーバーを開始します。
+    ///
+    /// サーバーは専用のスレッドで接続を受け付け、接続ごとにスレッドを作成して応答します。
+    /// リクエストを送らないまま接続し続けるクライアントがいても、他の接続への応答は妨げられず、
+    /// 読み書きが一定時間（5秒）進まない接続は切断されます。
+    /// 返された `Server` が破棄されると、新しい接続の受け付けを停止します。
+    /// ポート番号に `0` を指定した場合、実際に待ち受けているアドレスは `Server::local_addr` で取得できます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::openmetrics::Exporter;
+    /// use parallel_world::{Multiverse, World};
+    /// use std::io::{Read, Write};
+    /// use std::net::TcpStream;
+    /// use std::sync::Arc;
+    ///
+    /// let mv = Arc::new(Multiverse::new());
+    /// let exporter = Exporter::new(Arc::clone(&mv));
+    /// mv.add("idle".to_string(), World::from(|| ())).unwrap();
+    ///
+    /// let server = exporter.serve("127.0.0.1:0").unwrap();
+    /// // リクエストを送らないままの接続があっても、他の接続には応答する
+    /// let _idle = TcpStream::connect(server.local_addr()).unwrap();
+    /// let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    /// stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    /// let mut response = String::new();
+    /// stream.read_to_string(&mut response).unwrap();
+    ///
+    /// assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    /// assert!(response.contains("parallel_world_worlds{status=\"Ready\"} 1\n"));
+    /// drop(server);
+    /// ```
+    pub fn serve(&self, addr: impl ToSocketAddrs) -> io::Result<Server> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let exporter = self.clone();
+        let handle = {
+            let shutdown = Arc::clone(&shutdown);
+            thread::Builder::new()
+                .name("parallel_world-openmetrics".to_string())
+                .spawn(move || {
+                    for stream in listener.incoming() {
+                        if shutdown.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        // 1つの接続の失敗でサーバーを止めない
+                        if let Ok(stream) = stream {
+                            let exporter = exporter.clone();
+                            let _ = thread::Builder::new()
+                                .name("parallel_world-openmetrics-conn".to_string())
+                                .spawn(move || exporter.respond(stream));
+                        }
+                    }
+                })?
+        };
+        Ok(Server {
+            local_addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// 1つの HTTP リクエストを読み、応答を返します。
+    fn respond(&self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // ヘッダーは読み飛ばす
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+            header.clear();
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let (status, content_type, body) = match (parts.next(), parts.next()) {
+            (Some("GET"), Some("/metrics")) => ("200 OK", CONTENT_TYPE, self.render()),
+            (Some("GET"), _) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+            _ => (
+                "405 Method Not Allowed",
+                "text/plain",
+                "method not allowed\n".to_string(),
+            ),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        stream.flush()
+    }
+}
+
+/// # Server
+///
+/// `Exporter::serve` で開始した HTTP サーバーです。破棄されると停止します。
+pub struct Server {
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    /// 接続を待ち受けているアドレスを返します。
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // 接続の待ち受けから抜けさせるため、自分自身に接続する（すべてのアドレスで待ち受けている場合はループバックに）
+        let mut addr = self.local_addr;
+        if addr.ip().is_unspecified() {
+            addr.set_ip(match addr.ip() {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            });
+        }
+        // 接続できなければ待ち受けから抜けられないため、スレッドは待たずに手放す
+        let woken = TcpStream::connect_timeout(&addr, IO_TIMEOUT).is_ok();
+        if let Some(handle) = self.handle.take()
+            && woken
+        {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Counters {
+    /// 状態の変化を集計に加えます。
+    fn record(&mut self, event: &WorldEvent) {
+        match &event.to {
+            // 再試行からの再開は、新しい実行として数えない
+            WorldStatus::Running if !matches!(event.from, WorldStatus::Retrying { .. }) => {
+                self.started += 1;
+                self.running_since.insert(event.id.clone(), event.at);
+                return;
+            }
+            // 終了は実行中（依存待ち・実行待ちを含む）からの変化だけを数える（停止後の強制終了などを重複して数えない）
+            _ if !event.from.is_active() => return,
+            WorldStatus::Finished => self.finished += 1,
+            WorldStatus::Failed(_) => self.failed += 1,
+            WorldStatus::Stopped | WorldStatus::Killed | WorldStatus::TimedOut => self.stopped += 1,
+            _ => return,
+        }
+        if let Some(started_at) = self.running_since.remove(&event.id) {
+            self.durations
+                .observe(event.at.duration_since(started_at).as_secs_f64());
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in &mut self.buckets {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// すべてのメトリクスを `out` に書き込みます。
+fn write_metrics(
+    out: &mut String,
+    counts: &HashMap<&'static str, usize>,
+    counters: &Counters,
+) -> std::fmt::Result {
+    writeln!(out, "# TYPE {PREFIX}_worlds gauge")?;
+    writeln!(out, "# HELP {PREFIX}_worlds Number of worlds by status.")?;
+    for status in STATUSES {
+        let count = counts.get(status).copied().unwrap_or(0);
+        writeln!(out, "{PREFIX}_worlds{{status=\"{status}\"}} {count}")?;
+    }
+
+    for (name, help, value) in [
+        ("started", "Number of world runs started.", counters.started),
+        (
+            "finished",
+            "Number of world runs finished successfully.",
+            counters.finished,
+        ),
+        ("failed", "Number of world runs failed.", counters.failed),
+        (
+            "stopped",
+            "Number of world runs stopped, killed or timed out.",
+            counters.stopped,
+        ),
+    ] {
+        writeln!(out, "# TYPE {PREFIX}_worlds_{name} counter")?;
+        writeln!(out, "# HELP {PREFIX}_worlds_{name} {help}")?;
+        writeln!(out, "{PREFIX}_worlds_{name}_total {value}")?;
+    }
+
+    let histogram = &counters.durations;
+    let name = format!("{PREFIX}_world_duration_seconds");
+    writeln!(out, "# TYPE {name} histogram")?;
+    writeln!(out, "# UNIT {name} seconds")?;
+    writeln!(
+        out,
+        "# HELP {name} Wall-clock duration of completed world runs."
+    )?;
+    for (bound, count) in &histogram.buckets {
+        writeln!(out, "{name}_bucket{{le=\"{bound:?}\"}} {count}")?;
+    }
+    writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", histogram.count)?;
+    writeln!(out, "{name}_sum {:?}", histogram.sum)?;
+    writeln!(out, "{name}_count {}", histogram.count)?;
+    writeln!(out, "# EOF")
+}
@@ -1,7 +1,19 @@
+use crate::cancel;
+use crate::dependency::{self, Dependencies, DependencyPolicy, ForwardedResults};
+use crate::error::WorldError;
+use crate::event::WorldEvent;
+use crate::executor::Executor;
+use crate::logs::LogLine;
+use crate::metrics::MultiverseMetrics;
+use crate::report::Report;
+use crate::stream::Stream;
 use crate::world::{AnyWorld, World, WorldStatus};
-use std::any::Any;
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::future::{self, Future};
+use std::sync::{Arc, Mutex, mpsc};
+use std::task::Poll;
+use std::time::{Duration, Instant};
 
 /// # Multiverse
 ///
@@ -9,13 +21,26 @@ use std::sync::{Arc, Mutex};
 /// それらを並行して実行するための主要な構造体です。
 ///
 /// Pythonの`threading`モジュールのように、複数のタスクの開始、停止、状態監視を一元的に行えます。
-/// 各`World`は内部的に個別のスレッドで実行されます。
+/// 各`World`は内部的に個別のスレッドで実行されます（`with_executor` で作成した場合は、その実行器で実行されます）。
 pub struct Multiverse {
     /// WorldをID（String）で管理するHashMap。
     /// 異なる戻り値の型を持つWorldを管理するため、`AnyWorld`トレイトオブジェクトを使用します。
     worlds: Mutex<HashMap<String, Arc<dyn AnyWorld>>>,
+    /// 各Worldが依存するWorldのID（`add_with_deps` で追加されたもの）
+    dependencies: Mutex<HashMap<String, Dependencies>>,
+    /// 下流のWorldに渡すために取り出された結果（`World::after` / `World::after2`）
+    forwarded: ForwardedResults,
+    /// 追加されたWorldを実行する実行器（`with_executor` で設定されたもの）
+    executor: Option<Arc<dyn Executor>>,
+    /// 状態の変化を通知する購読者（`subscribe` で追加されたもの）
+    subscribers: Subscribers,
 }
 
+/// 状態の変化を受け取る関数。`false` を返すと購読を解除します。
+pub(crate) type EventCallback = Box<dyn Fn(&WorldEvent) -> bool + Send + 'static>;
+/// 状態の変化の購読者の一覧
+type Subscribers = Arc<Mutex<Vec<EventCallback>>>;
+
 impl Multiverse {
     /// 新しい空の `Multiverse` インスタンスを生成します。
     ///
@@ -31,12 +56,44 @@ impl Multiverse {
     pub fn new() -> Self {
         Multiverse {
             worlds: Mutex::new(HashMap::new()),
+            dependencies: Mutex::new(HashMap::new()),
+            forwarded: Arc::new(Mutex::new(HashMap::new())),
+            executor: None,
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// 追加されたWorldを `executor` で実行する、新しい空の `Multiverse` インスタンスを生成します。
+    ///
+    /// `ThreadPool` を渡すと、Worldの数に関わらず同時に使用するスレッドの数を制限できます。
+    /// ワーカーの空きを待っているWorldは `WorldStatus::Queued` 状態になります。
+    /// `World::with_executor` で実行器が設定されたWorldは、その実行器で実行されます。
+    ///
+    /// キューで待っている時間も `World::with_timeout` / `World::with_deadline` の期限に含まれます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, ThreadPool, World};
+    ///
+    /// let mv = Multiverse::with_executor(ThreadPool::new(4));
+    /// for i in 0..100 {
+    ///     mv.add(format!("world{i}"), World::from(move || i * 2)).unwrap();
+    /// }
+    /// mv.start_all();
+    /// assert_eq!(mv.status::<i32>("world99"), Ok(198));
+    /// ```
+    pub fn with_executor<E: Executor + 'static>(executor: E) -> Self {
+        Multiverse {
+            executor: Some(Arc::new(executor)),
+            ..Multiverse::new()
         }
     }
 
     /// `Multiverse` に新しい `World` を追加します。
     /// 同じIDのWorldが既に存在する場合はエラーを返します。
     ///
+    /// `World::after` / `World::after2` で作成されたWorldは、上流のWorldに依存するWorldとして追加されます。
+    ///
     /// # 型引数
     /// * `R` - 追加するWorldが返す結果の型。
     ///
@@ -59,20 +116,262 @@ impl Multiverse {
     /// let world_duplicate: World<()> = World::new(); // 戻り値の型を明示
     /// assert!(pw.add("task_one".to_string(), world_duplicate).is_err());
     /// ```
+    ///
+    /// `tracing` フィーチャーが有効な場合、WorldのスパンにはIDが `id` フィールドとして記録されます。
+    ///
+    /// ```
+    /// # #[cfg(feature = "tracing")] {
+    /// use parallel_world::{Multiverse, World};
+    /// use std::sync::{Arc, Mutex};
+    /// use tracing::field::{Field, Visit};
+    /// use tracing::span::{Attributes, Id, Record};
+    /// use tracing::{Event, Metadata, Subscriber};
+    ///
+    /// // 実行中に入ったスパンの `id` フィールドを記録する、最小限のサブスクライバー
+    /// #[derive(Default)]
+    /// struct Recorder {
+    ///     /// スパンごとの `id` フィールド（スパンのIDは添字 + 1）
+    ///     spans: Mutex<Vec<Option<String>>>,
+    ///     entered: Mutex<Vec<String>>,
+    /// }
+    ///
+    /// struct IdField<'a>(&'a mut Option<String>);
+    ///
+    /// impl Visit for IdField<'_> {
+    ///     fn record_str(&mut self, field: &Field, value: &str) {
+    ///         if field.name() == "id" {
+    ///             *self.0 = Some(value.to_string());
+    ///         }
+    ///     }
+    ///     fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+    /// }
+    ///
+    /// impl Subscriber for Recorder {
+    ///     fn enabled(&self, _: &Metadata<'_>) -> bool {
+    ///         true
+    ///     }
+    ///     fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+    ///         let mut spans = self.spans.lock().unwrap();
+    ///         let mut id = None;
+    ///         attrs.record(&mut IdField(&mut id));
+    ///         spans.push(id);
+    ///         Id::from_u64(spans.len() as u64)
+    ///     }
+    ///     fn record(&self, span: &Id, values: &Record<'_>) {
+    ///         let mut spans = self.spans.lock().unwrap();
+    ///         values.record(&mut IdField(&mut spans[span.into_u64() as usize - 1]));
+    ///     }
+    ///     fn record_follows_from(&self, _: &Id, _: &Id) {}
+    ///     fn event(&self, _: &Event<'_>) {}
+    ///     fn enter(&self, span: &Id) {
+    ///         let id = self.spans.lock().unwrap()[span.into_u64() as usize - 1].clone();
+    ///         self.entered.lock().unwrap().extend(id);
+    ///     }
+    ///     fn exit(&self, _: &Id) {}
+    /// }
+    ///
+    /// let recorder = Arc::new(Recorder::default());
+    /// tracing::subscriber::with_default(Arc::clone(&recorder), || {
+    ///     let pw = Multiverse::new();
+    ///     pw.add("fetch".to_string(), World::from(|| 1)).unwrap();
+    ///     pw.start_all();
+    ///     assert_eq!(pw.status::<i32>("fetch"), Ok(1));
+    /// });
+    /// // 実行スレッドでは、IDが記録されたスパンの中でクロージャが実行される
+    /// assert_eq!(*recorder.entered.lock().unwrap(), ["fetch"]);
+    /// # }
+    /// ```
     pub fn add<R: Send + 'static + std::any::Any>(
         &self,
         id: String,
         world: World<R>,
-    ) -> Result<(), String> {
+    ) -> Result<(), WorldError> {
+        self.add_with_deps(id, world, &[])
+    }
+
+    /// 期限付きで `World` を追加します。
+    ///
+    /// `deadline` までに結果を返さなかったWorldは `WorldStatus::TimedOut` に遷移し、
+    /// キャンセルトークンがキャンセルされます。`status` は期限を超えてブロックせず、エラーを返します。
+    /// 詳しくは `World::with_deadline` を参照してください。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldStatus};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let pw = Multiverse::new();
+    /// let deadline = Instant::now() + Duration::from_millis(50);
+    /// pw.add_with_deadline("wedged".to_string(), World::with_cancel(|token| {
+    ///     while !token.sleep_or_cancel(Duration::from_millis(10)) {}
+    /// }), deadline).unwrap();
+    ///
+    /// pw.exec("wedged").unwrap();
+    /// assert!(pw.status::<()>("wedged").is_err()); // 期限で打ち切られる
+    /// assert_eq!(pw.progress("wedged").unwrap(), WorldStatus::TimedOut);
+    /// ```
+    pub fn add_with_deadline<R: Send + 'static + std::any::Any>(
+        &self,
+        id: String,
+        world: World<R>,
+        deadline: Instant,
+    ) -> Result<(), WorldError> {
+        self.add(id, world.with_deadline(deadline))
+    }
+
+    /// 他のWorldに依存する `World` を追加します。
+    ///
+    /// このWorldは、`deps` のすべてのWorldが `WorldStatus::Finished` になってから実行開始されます。
+    /// `exec` や `start_all` で開始すると、依存先が完了するまでは `WorldStatus::Blocked` 状態で待機します。
+    /// 依存先が失敗した場合の扱いは `set_dependency_policy` で設定できます（既定は `DependencyPolicy::Skip`）。
+    ///
+    /// 依存先はまだ追加されていないWorldでも構いませんが、開始する時点では追加されている必要があります。
+    /// `World::after` / `World::after2` で作成されたWorldは、`deps` に加えて上流のWorldにも依存します。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::AlreadyExists)` - 同じIDのWorldが既に存在する場合に返されます。
+    /// * `Err(WorldError::DependencyCycle)` - 依存関係が循環する場合に返されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldError, WorldStatus};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::new();
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let (l1, l2, l3) = (Arc::clone(&log), Arc::clone(&log), Arc::clone(&log));
+    ///
+    /// pw.add_with_deps("deploy".to_string(), World::from(move || l1.lock().unwrap().push("deploy")), &["build", "test"]).unwrap();
+    /// pw.add_with_deps("test".to_string(), World::from(move || l2.lock().unwrap().push("test")), &["build"]).unwrap();
+    /// pw.add("build".to_string(), World::from(move || {
+    ///     sleep(Duration::from_millis(50));
+    ///     l3.lock().unwrap().push("build");
+    /// })).unwrap();
+    ///
+    /// pw.start_all();
+    /// assert_eq!(pw.progress("deploy").unwrap(), WorldStatus::Blocked);
+    ///
+    /// pw.status::<()>("deploy").unwrap();
+    /// assert_eq!(*log.lock().unwrap(), vec!["build", "test", "deploy"]);
+    ///
+    /// // 循環する依存関係は追加できない
+    /// pw.add_with_deps("a".to_string(), World::from(|| ()), &["b"]).unwrap();
+    /// assert_eq!(
+    ///     pw.add_with_deps("b".to_string(), World::from(|| ()), &["a"]),
+    ///     Err(WorldError::DependencyCycle(vec!["b".to_string(), "a".to_string(), "b".to_string()]))
+    /// );
+    /// ```
+    pub fn add_with_deps<R: Send + 'static + std::any::Any>(
+        &self,
+        id: String,
+        world: World<R>,
+        deps: &[&str],
+    ) -> Result<(), WorldError> {
         let mut worlds_guard = self.worlds.lock().unwrap();
         if worlds_guard.contains_key(&id) {
-            return Err(format!("World with ID '{}' already exists.", id));
+            return Err(WorldError::AlreadyExists(id));
+        }
+        let mut ids = world.upstream_ids();
+        for dep in deps {
+            if !ids.iter().any(|id| id == dep) {
+                ids.push(dep.to_string());
+            }
+        }
+        if !ids.is_empty() {
+            let mut dependencies_guard = self.dependencies.lock().unwrap();
+            if let Some(cycle) = dependency::find_cycle(&dependencies_guard, &id, &ids) {
+                return Err(WorldError::DependencyCycle(cycle));
+            }
+            dependencies_guard.entry(id.clone()).or_default().ids = ids;
         }
         // World<R>をArc<dyn AnyWorld>にダウンキャストして挿入
+        let world = world.or_executor(self.executor.as_ref());
+        #[cfg(feature = "tracing")]
+        world.set_trace_id(&id);
+        let subscribers = Arc::clone(&self.subscribers);
+        let world_id = id.clone();
+        world.set_status_listener(Arc::new(move |from, to| {
+            publish(&subscribers, &world_id, from, to)
+        }));
         worlds_guard.insert(id, Arc::new(world));
         Ok(())
     }
 
+    /// 指定されたIDのWorldについて、依存先が失敗したときの扱いを設定します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{DependencyPolicy, Multiverse, World, WorldError, WorldStatus};
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("build".to_string(), World::from(|| -> () { panic!("compile error") })).unwrap();
+    /// pw.add_with_deps("test".to_string(), World::from(|| ()), &["build"]).unwrap();
+    /// pw.add_with_deps("report".to_string(), World::from(|| "report"), &["build"]).unwrap();
+    /// pw.add_with_deps("notify".to_string(), World::from(|| ()), &["build"]).unwrap();
+    /// pw.set_dependency_policy("report", DependencyPolicy::RunAnyway).unwrap();
+    /// pw.set_dependency_policy("notify", DependencyPolicy::Fail).unwrap();
+    ///
+    /// pw.start_all();
+    /// assert_eq!(pw.status::<()>("test"), Err(WorldError::Skipped));
+    /// assert_eq!(pw.progress("test").unwrap(), WorldStatus::Skipped);
+    /// assert_eq!(pw.status::<&str>("report"), Ok("report"));
+    ///
+    /// let Err(WorldError::Failed(failure)) = pw.status::<()>("notify") else { panic!() };
+    /// assert_eq!(
+    ///     failure.downcast_error::<WorldError>(),
+    ///     Some(&WorldError::DependencyFailed("build".to_string()))
+    /// );
+    /// ```
+    pub fn set_dependency_policy(
+        &self,
+        id: &str,
+        policy: DependencyPolicy,
+    ) -> Result<(), WorldError> {
+        if self.get(id).is_none() {
+            return Err(WorldError::NotFound(id.to_string()));
+        }
+        let mut dependencies_guard = self.dependencies.lock().unwrap();
+        dependencies_guard.entry(id.to_string()).or_default().policy = policy;
+        Ok(())
+    }
+
+    /// 指定されたIDのWorldの優先度を変更します（`World::set_priority` を参照）。
+    ///
+    /// 実行器のキューで待っているWorldに対して呼び出すと、次に実行するWorldを選ぶときから反映されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, ThreadPool, World};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::with_executor(ThreadPool::new(1));
+    /// let order = Arc::new(Mutex::new(Vec::new()));
+    /// pw.add("busy".to_string(), World::from(|| sleep(Duration::from_millis(50)))).unwrap();
+    /// for id in ["first", "second"] {
+    ///     let order = Arc::clone(&order);
+    ///     pw.add(id.to_string(), World::from(move || order.lock().unwrap().push(id))).unwrap();
+    /// }
+    /// pw.exec("busy").unwrap();
+    /// pw.exec("first").unwrap();
+    /// pw.exec("second").unwrap();
+    ///
+    /// // キューで待っている間に、後から来たWorldの優先度を上げる
+    /// pw.set_priority("second", 1).unwrap();
+    /// pw.wait_all(&["busy", "first", "second"]).unwrap();
+    /// assert_eq!(*order.lock().unwrap(), ["second", "first"]);
+    /// ```
+    pub fn set_priority(&self, id: &str, priority: i32) -> Result<(), WorldError> {
+        let world = self
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))?;
+        world.any_set_priority(priority);
+        Ok(())
+    }
+
     /// `Multiverse` から指定されたIDの `World` を削除します。
     ///
     /// Worldが実行中の場合、削除することはできません。まず `stop_all` または `kill` メソッドで
@@ -83,7 +382,8 @@ impl Multiverse {
     ///
     /// # 戻り値
     /// `Ok(())` - Worldが正常に削除された場合。
-    /// `Err(String)` - 指定されたIDのWorldが見つからない場合、またはWorldが実行中の場合。
+    /// `Err(WorldError::NotFound)` - 指定されたIDのWorldが見つからない場合。
+    /// `Err(WorldError::AlreadyRunning)` - Worldが実行中の場合。
     ///
     /// # 例
     /// ```
@@ -108,19 +408,18 @@ impl Multiverse {
     /// // 存在しないWorldの削除はエラー
     /// assert!(pw.del("non_existent_task").is_err());
     /// ```
-    pub fn del(&self, id: &str) -> Result<(), String> {
+    pub fn del(&self, id: &str) -> Result<(), WorldError> {
         let mut worlds_guard = self.worlds.lock().unwrap();
         if let Some(world) = worlds_guard.get(id) {
-            if world.any_progress() == WorldStatus::Running {
-                return Err(format!(
-                    "Cannot delete running World with ID '{}'. Stop it first.",
-                    id
-                ));
+            if world.any_progress().is_active() {
+                return Err(WorldError::AlreadyRunning);
             }
             worlds_guard.remove(id);
+            self.dependencies.lock().unwrap().remove(id);
+            self.forwarded.lock().unwrap().remove(id);
             Ok(())
         } else {
-            Err(format!("World with ID '{}' not found.", id))
+            Err(WorldError::NotFound(id.to_string()))
         }
     }
 
@@ -153,8 +452,9 @@ impl Multiverse {
 
     /// 登録されているすべての `World` のうち、状態が `Ready` のものを一括で実行開始します。
     ///
-    /// 各Worldの`start()`メソッドを呼び出しますが、個々のWorldで発生した開始エラーは無視されます。
+    /// 各Worldを `exec` と同様に開始しますが、個々のWorldで発生した開始エラーは無視されます。
     /// それぞれのWorldのログや`progress()`メソッドで状態を確認してください。
+    /// 依存関係のあるWorld（`add_with_deps`）は、依存先が完了するまで `Blocked` 状態で待機します。
     ///
     /// # 例
     /// ```
@@ -179,18 +479,25 @@ impl Multiverse {
     /// assert_eq!(pw.progress("task_b").unwrap(), WorldStatus::Finished);
     /// ```
     pub fn start_all(&self) {
-        let worlds_guard = self.worlds.lock().unwrap();
-        for (_, world) in worlds_guard.iter() {
-            if world.any_progress() == WorldStatus::Ready {
-                let _ = world.any_start(); // エラーは無視（個々のWorldのログで対応）
-            }
+        let ready: Vec<String> = self
+            .worlds
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, world)| world.any_progress() == WorldStatus::Ready)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in ready {
+            let _ = self.exec(&id); // エラーは無視（個々のWorldのログで対応）
         }
     }
 
     /// 特定のWorldを実行開始します。
     ///
+    /// 依存関係のあるWorld（`add_with_deps`）は `Blocked` 状態になり、依存先が完了してから実行開始されます。
+    ///
     /// # Errors
-    /// Worldが見つからない、または既に実行中の場合にエラーを返します。
+    /// Worldまたはその依存先が見つからない、または既に実行中の場合にエラーを返します。
     ///
     /// # 例
     /// ```
@@ -213,19 +520,387 @@ impl Multiverse {
     /// pw.status::<i32>("my_task").unwrap(); // 完了を待つ
     /// assert_eq!(pw.progress("my_task").unwrap(), WorldStatus::Finished);
     /// ```
-    pub fn exec(&self, id: &str) -> Result<(), String> {
-        if let Some(world) = self.get(id) {
-            world.any_start()
-        } else {
-            Err(format!("World with ID '{}' not found.", id))
+    pub fn exec(&self, id: &str) -> Result<(), WorldError> {
+        let world = self
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))?;
+        let dependencies = self.dependencies.lock().unwrap().get(id).cloned();
+        let Some(dependencies) = dependencies.filter(|d| !d.ids.is_empty()) else {
+            return world.any_start();
+        };
+
+        let ids: Vec<&str> = dependencies.ids.iter().map(String::as_str).collect();
+        let deps = self.get_all(&ids)?;
+        if !world.any_block() {
+            return world.any_start(); // Ready以外の状態に応じたエラーを返す
         }
+        let forwarded = Arc::clone(&self.forwarded);
+        dependency::await_dependencies(world, deps, dependencies.policy, forwarded);
+        Ok(())
+    }
+
+    /// 完了・失敗・停止したWorldを `Ready` 状態に戻し、同じIDのまま再び実行開始します。
+    ///
+    /// Worldは `World::repeatable` で作成されている必要があります（`World::reset` を参照）。
+    /// まだ実行されていないWorldは、そのまま実行開始します。
+    ///
+    /// # Errors
+    /// Worldが見つからない、実行中である、または再実行できない場合にエラーを返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldStatus};
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("job".to_string(), World::repeatable(|| 42)).unwrap();
+    ///
+    /// pw.exec("job").unwrap();
+    /// assert_eq!(pw.status::<i32>("job"), Ok(42));
+    ///
+    /// pw.restart("job").unwrap();
+    /// assert_eq!(pw.status::<i32>("job"), Ok(42));
+    /// assert_eq!(pw.history("job").unwrap(), vec![WorldStatus::Finished]);
+    /// ```
+    pub fn restart(&self, id: &str) -> Result<(), WorldError> {
+        let world = self
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))?;
+        world.any_reset()?;
+        self.forwarded.lock().unwrap().remove(id); // 前回の実行の結果は破棄する
+        self.exec(id)
+    }
+
+    /// 登録されているすべてのWorldについて、報告された進捗をIDごとに取得します。
+    ///
+    /// 詳しくは `World::report` / `World::with_reporter` を参照してください。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World};
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("import".to_string(), World::with_reporter(|reporter| {
+    ///     reporter.set_step(420, 1000);
+    ///     reporter.set_message("importing users");
+    /// })).unwrap();
+    /// pw.add("plain".to_string(), World::from(|| ())).unwrap();
+    /// pw.start_all();
+    /// pw.wait_all(&["import", "plain"]).unwrap();
+    ///
+    /// let reports = pw.reports();
+    /// assert_eq!(reports["import"].fraction, Some(0.42));
+    /// assert_eq!(reports["import"].message.as_deref(), Some("importing users"));
+    /// assert_eq!(reports["plain"].fraction, None);
+    /// ```
+    pub fn reports(&self) -> HashMap<String, Report> {
+        let worlds_guard = self.worlds.lock().unwrap();
+        worlds_guard
+            .iter()
+            .map(|(id, world)| (id.clone(), world.any_report()))
+            .collect()
+    }
+
+    /// 指定されたIDのWorldが出力したログを古い順に取得します（`World::logs` を参照）。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NotFound)` - 指定されたIDのWorldが存在しない場合に返されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World};
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("import".to_string(), World::with_logger(|logger| -> () {
+    ///     logger.log("reading users.csv");
+    ///     panic!("unexpected end of file");
+    /// })).unwrap();
+    /// pw.exec("import").unwrap();
+    /// assert!(pw.status::<()>("import").is_err());
+    ///
+    /// // 失敗した後でも、何を出力していたかを確認できる
+    /// let logs = pw.logs("import").unwrap();
+    /// assert_eq!(logs[0].text, "reading users.csv");
+    /// ```
+    pub fn logs(&self, id: &str) -> Result<Vec<LogLine>, WorldError> {
+        self.get(id)
+            .map(|world| world.any_logs())
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))
+    }
+
+    /// 指定されたIDのWorldが出力したログを、出力されるそばから受け取るレシーバーを返します
+    /// （`World::follow_logs` を参照）。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NotFound)` - 指定されたIDのWorldが存在しない場合に返されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::logs::LogStream;
+    /// use parallel_world::{Multiverse, World};
+    /// use std::process::Command;
+    ///
+    /// let pw = Multiverse::new();
+    /// let mut cmd = Command::new("sh");
+    /// cmd.args(["-c", "echo out; echo err >&2"]);
+    /// pw.add("script".to_string(), World::in_process(cmd)).unwrap();
+    /// pw.exec("script").unwrap();
+    ///
+    /// let mut lines: Vec<_> = pw
+    ///     .follow_logs("script")
+    ///     .unwrap()
+    ///     .into_iter()
+    ///     .map(|line| (line.stream, line.text))
+    ///     .collect();
+    /// lines.sort_by_key(|(stream, _)| *stream == LogStream::Stderr);
+    /// assert_eq!(
+    ///     lines,
+    ///     [(LogStream::Stdout, "out".to_string()), (LogStream::Stderr, "err".to_string())]
+    /// );
+    /// ```
+    pub fn follow_logs(&self, id: &str) -> Result<mpsc::Receiver<LogLine>, WorldError> {
+        self.get(id)
+            .map(|world| world.any_follow_logs())
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))
+    }
+
+    /// 指定されたIDのWorld（`World::with_inbox` で作成されたもの）の受信箱にメッセージを送ります。
+    ///
+    /// 他のWorldからメッセージを送るには、`Arc<Multiverse>` をクロージャに渡してください。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NotFound)` - 指定されたIDのWorldが存在しない場合に返されます。
+    /// * `Err(WorldError::NoInbox)` - `World::with_inbox` で作成されたWorldではない場合に返されます。
+    /// * `Err(WorldError::DowncastFailed)` - `M` が受信箱のメッセージの型と異なる場合に返されます。
+    /// * `Err(WorldError::InboxClosed)` - Worldが停止・完了している場合に返されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldError};
+    /// use std::sync::Arc;
+    ///
+    /// enum Counter {
+    ///     Add(i64),
+    ///     Finish,
+    /// }
+    ///
+    /// let pw = Arc::new(Multiverse::new());
+    /// pw.add("counter".to_string(), World::with_inbox(|inbox| {
+    ///     let mut count = 0;
+    ///     for message in inbox {
+    ///         match message {
+    ///             Counter::Add(n) => count += n,
+    ///             Counter::Finish => break,
+    ///         }
+    ///     }
+    ///     count
+    /// })).unwrap();
+    ///
+    /// // 他のWorldからも送れる
+    /// let mv = Arc::clone(&pw);
+    /// pw.add("producer".to_string(), World::from(move || {
+    ///     for n in 1..=4 {
+    ///         mv.send("counter", Counter::Add(n)).unwrap();
+    ///     }
+    /// })).unwrap();
+    ///
+    /// pw.start_all();
+    /// pw.status::<()>("producer").unwrap();
+    /// assert!(matches!(pw.send("counter", "finish"), Err(WorldError::DowncastFailed { .. })));
+    /// pw.send("counter", Counter::Finish).unwrap();
+    /// assert_eq!(pw.status::<i64>("counter"), Ok(10));
+    /// assert_eq!(pw.send("counter", Counter::Add(1)), Err(WorldError::InboxClosed));
+    /// ```
+    pub fn send<M: Send + 'static>(&self, id: &str, message: M) -> Result<(), WorldError> {
+        let world = self
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))?;
+        world.any_send(Box::new(message), std::any::type_name::<M>())
+    }
+
+    /// 指定されたIDのWorld（`World::with_emitter` で作成されたもの）が送り出すアイテムを受け取る `Stream` を取り出します。
+    ///
+    /// `Stream` を取り出せるのは一度だけです。すべてのアイテムを受け取った後、Worldの要約（クロージャの戻り値）は
+    /// `status` などで取得できます。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NotFound)` - 指定されたIDのWorldが存在しない場合に返されます。
+    /// * `Err(WorldError::NoStream)` - `World::with_emitter` で作成されたWorldではない場合に返されます。
+    /// * `Err(WorldError::StreamAlreadyTaken)` - `Stream` が既に取り出されている場合に返されます。
+    /// * `Err(WorldError::DowncastFailed)` - `T` が送り出されるアイテムの型と異なる場合に返されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldError};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::new();
+    /// // バッファは2個までなので、消費側が取り出すまで送り出す側が待たされる
+    /// pw.add("poller".to_string(), World::with_emitter(2, |emitter| {
+    ///     (1..=10).take_while(|&i| emitter.emit(i)).count()
+    /// })).unwrap();
+    /// pw.exec("poller").unwrap();
+    ///
+    /// sleep(Duration::from_millis(20));
+    /// assert!(pw.progress("poller").unwrap().is_active());
+    ///
+    /// assert!(matches!(pw.stream::<String>("poller"), Err(WorldError::DowncastFailed { .. })));
+    /// let total: i32 = pw.stream::<i32>("poller").unwrap().sum();
+    /// assert_eq!(total, 55);
+    /// assert_eq!(pw.status::<usize>("poller"), Ok(10));
+    /// assert!(matches!(pw.stream::<i32>("poller"), Err(WorldError::StreamAlreadyTaken)));
+    /// ```
+    pub fn stream<T: Send + 'static>(&self, id: &str) -> Result<Stream<T>, WorldError> {
+        let world = self
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))?;
+        let consumer = world.any_take_stream(TypeId::of::<T>(), std::any::type_name::<T>())?;
+        Ok(*consumer
+            .downcast::<Stream<T>>()
+            .expect("item type was checked"))
+    }
+
+    /// 登録されているすべてのWorldの実行時間の記録と、その集計を取得します。
+    ///
+    /// 状態ごとのWorldの数と、実行が終わったWorldの実行時間の中央値・95パーセンタイルが集計されます。
+    /// 各Worldの記録については `World::metrics` を参照してください。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::new();
+    /// for i in 1..=3 {
+    ///     pw.add(format!("sleep{i}"), World::from(move || sleep(Duration::from_millis(10 * i)))).unwrap();
+    /// }
+    /// pw.add("broken".to_string(), World::from(|| -> () { panic!("boom") })).unwrap();
+    /// pw.start_all();
+    /// pw.wait_all(&["sleep1", "sleep2", "sleep3", "broken"]).unwrap();
+    ///
+    /// let metrics = pw.metrics();
+    /// assert_eq!(metrics.counts["Finished"], 3);
+    /// assert_eq!(metrics.counts["Failed"], 1);
+    /// assert!(metrics.p50.unwrap() >= Duration::from_millis(10));
+    /// assert!(metrics.p95.unwrap() >= Duration::from_millis(30));
+    /// assert!(metrics.worlds["sleep3"].duration().unwrap() >= Duration::from_millis(30));
+    /// ```
+    pub fn metrics(&self) -> MultiverseMetrics {
+        let worlds_guard = self.worlds.lock().unwrap();
+        let worlds = worlds_guard
+            .iter()
+            .map(|(id, world)| (id.clone(), world.any_progress().name(), world.any_metrics()))
+            .collect();
+        MultiverseMetrics::aggregate(worlds)
+    }
+
+    /// 登録されたWorldの状態の変化を受け取るレシーバーを返します。
+    ///
+    /// `start` / `stop` / 実行スレッドの終了などによる状態の変化が、発生するたびに `WorldEvent` として送られます。
+    /// 同じWorldのイベントは発生した順に届きます。`progress` のポーリングでは見逃してしまう、
+    /// `Running` から `Finished` への素早い変化も受け取れます。
+    /// レシーバーを破棄すると、購読は解除されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldStatus};
+    ///
+    /// let pw = Multiverse::new();
+    /// let events = pw.subscribe();
+    /// pw.add("quick".to_string(), World::from(|| 1)).unwrap();
+    /// pw.start_all();
+    /// pw.status::<i32>("quick").unwrap();
+    ///
+    /// let transitions: Vec<_> = events.try_iter().map(|e| (e.id, e.from, e.to)).collect();
+    /// assert_eq!(
+    ///     transitions,
+    ///     vec![
+    ///         ("quick".to_string(), WorldStatus::Ready, WorldStatus::Running),
+    ///         ("quick".to_string(), WorldStatus::Running, WorldStatus::Finished),
+    ///     ]
+    /// );
+    /// ```
+    pub fn subscribe(&self) -> mpsc::Receiver<WorldEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.on_event(Box::new(move |event| tx.send(event.clone()).is_ok()));
+        rx
+    }
+
+    /// 登録されたWorldの状態が変化するたびに、変化したスレッドで `callback` を呼び出します。
+    ///
+    /// `callback` はWorldの状態をロックしたまま呼び出されるため、Worldの状態を参照してはいけません。
+    pub(crate) fn on_event(&self, callback: EventCallback) {
+        self.subscribers.lock().unwrap().push(callback);
+    }
+
+    /// 指定されたIDのWorldの、これまでの実行の最終状態を古い順に取得します。
+    ///
+    /// 詳しくは `World::history` を参照してください。
+    pub fn history(&self, id: &str) -> Result<Vec<WorldStatus>, WorldError> {
+        self.get(id)
+            .map(|world| world.any_history())
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))
     }
 
     /// すべての実行中のWorldを停止します。
+    ///
+    /// 各Worldのキャンセルトークンがキャンセルされるため、
+    /// `World::with_cancel` で作成されたWorldは協調的に終了できます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldStatus};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::new();
+    /// for id in ["worker_1", "worker_2"] {
+    ///     pw.add(id.to_string(), World::with_cancel(|token| {
+    ///         while !token.sleep_or_cancel(Duration::from_millis(10)) {}
+    ///     })).unwrap();
+    /// }
+    ///
+    /// pw.start_all();
+    /// sleep(Duration::from_millis(20));
+    /// pw.stop_all();
+    ///
+    /// pw.status::<()>("worker_1").unwrap(); // キャンセルにより速やかに終了する
+    /// pw.status::<()>("worker_2").unwrap();
+    /// assert_eq!(pw.progress("worker_1").unwrap(), WorldStatus::Stopped);
+    /// ```
+    ///
+    /// 停止時に呼び出されるコールバックから、Multiverseを操作することもできます。
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldStatus};
+    /// use std::sync::mpsc;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// let mv = Arc::new(Multiverse::new());
+    /// let seen = Arc::new(Mutex::new(None));
+    /// let (ready_tx, ready_rx) = mpsc::channel();
+    /// let (weak, seen_clone) = (Arc::downgrade(&mv), Arc::clone(&seen));
+    /// mv.add("a".to_string(), World::with_cancel(move |token| {
+    ///     token.on_cancel(move || {
+    ///         let mv = weak.upgrade().unwrap();
+    ///         *seen_clone.lock().unwrap() = mv.progress("a").ok();
+    ///     });
+    ///     ready_tx.send(()).unwrap();
+    ///     while !token.sleep_or_cancel(Duration::from_millis(10)) {}
+    /// })).unwrap();
+    ///
+    /// mv.start_all();
+    /// ready_rx.recv().unwrap(); // コールバックが登録されるのを待つ
+    /// mv.stop_all();
+    /// assert_eq!(*seen.lock().unwrap(), Some(WorldStatus::Stopped));
+    /// ```
     pub fn stop_all(&self) {
-        let worlds_guard = self.worlds.lock().unwrap();
-        for (_, world) in worlds_guard.iter() {
-            if world.any_progress() == WorldStatus::Running {
+        // 停止時に呼び出されるコールバックからMultiverseを操作できるよう、ロックを解放してから停止する
+        let worlds: Vec<Arc<dyn AnyWorld>> =
+            self.worlds.lock().unwrap().values().cloned().collect();
+        for world in worlds {
+            if world.any_progress().is_active() {
                 let _ = world.any_stop(); // エラーは無視
             }
         }
@@ -233,6 +908,10 @@ impl Multiverse {
 
     /// 特定のWorldを停止します。
     ///
+    /// Worldのキャンセルトークンがキャンセルされ、状態は`Stopped`になります。
+    /// `World::in_process` で作成されたプロセスベースのWorldの場合は、子プロセスを強制終了し、
+    /// 状態は`Killed`になります（`World::kill`を参照）。
+    ///
     /// # Errors
     /// Worldが見つからない、または実行中でない場合にエラーを返します。
     ///
@@ -269,11 +948,11 @@ impl Multiverse {
     /// // 存在しないWorldのkillはエラー
     /// assert!(pw.kill("non_existent_task").is_err());
     /// ```
-    pub fn kill(&self, id: &str) -> Result<(), String> {
+    pub fn kill(&self, id: &str) -> Result<(), WorldError> {
         if let Some(world) = self.get(id) {
-            world.any_stop()
+            world.any_kill()
         } else {
-            Err(format!("World with ID '{}' not found.", id))
+            Err(WorldError::NotFound(id.to_string()))
         }
     }
 
@@ -301,11 +980,11 @@ impl Multiverse {
     ///
     /// assert!(pw.progress("non_existent_task").is_err());
     /// ```
-    pub fn progress(&self, id: &str) -> Result<WorldStatus, String> {
+    pub fn progress(&self, id: &str) -> Result<WorldStatus, WorldError> {
         if let Some(world) = self.get(id) {
             Ok(world.any_progress())
         } else {
-            Err(format!("World with ID '{}' not found.", id))
+            Err(WorldError::NotFound(id.to_string()))
         }
     }
 
@@ -316,11 +995,11 @@ impl Multiverse {
     ///
     /// # Errors
     /// Worldが見つからない、タスクが失敗した、または結果のダウンキャストに失敗した場合にエラーを返します。
-    pub fn status_any(&self, id: &str) -> Result<Box<dyn Any + Send>, String> {
+    pub fn status_any(&self, id: &str) -> Result<Box<dyn Any + Send>, WorldError> {
         if let Some(world) = self.get(id) {
             world.any_status()
         } else {
-            Err(format!("World with ID '{}' not found.", id))
+            Err(WorldError::NotFound(id.to_string()))
         }
     }
 
@@ -334,11 +1013,13 @@ impl Multiverse {
     /// * `T` - 期待される戻り値の型。
     ///
     /// # エラー
-    /// Worldが見つからない、タスクが失敗した、または結果のダウンキャストに失敗した場合にエラーを返します。
+    /// * `WorldError::NotFound` - Worldが見つからない場合。
+    /// * `WorldError::DowncastFailed` - 結果のダウンキャストに失敗した場合。
+    /// * その他 - タスクが失敗・停止した場合など、`World::status` が返すエラー。
     ///
     /// # 例
     /// ```
-    /// use parallel_world::{Multiverse, World};
+    /// use parallel_world::{Multiverse, World, WorldError};
     /// use std::thread::sleep;
     /// use std::time::Duration;
     ///
@@ -350,7 +1031,7 @@ impl Multiverse {
     ///     10 + 20
     /// })).unwrap();
     /// pw.exec("sum_task").unwrap();
-    /// let sum_result: Result<i32, String> = pw.status("sum_task");
+    /// let sum_result: Result<i32, WorldError> = pw.status("sum_task");
     /// assert!(sum_result.is_ok());
     /// assert_eq!(sum_result.unwrap(), 30);
     ///
@@ -360,7 +1041,7 @@ impl Multiverse {
     ///     "Task finished!".to_string()
     /// })).unwrap();
     /// pw.exec("message_task").unwrap();
-    /// let msg_result: Result<String, String> = pw.status("message_task");
+    /// let msg_result: Result<String, WorldError> = pw.status("message_task");
     /// assert!(msg_result.is_ok());
     /// assert_eq!(msg_result.unwrap(), "Task finished!".to_string());
     ///
@@ -370,24 +1051,413 @@ impl Multiverse {
     ///     0 // ダミーの戻り値
     /// })).unwrap();
     /// pw.exec("fail_task").unwrap();
-    /// let fail_result: Result<i32, String> = pw.status("fail_task");
+    /// let fail_result: Result<i32, WorldError> = pw.status("fail_task");
     /// assert!(fail_result.is_err());
     /// println!("Fail task error: {}", fail_result.unwrap_err());
     ///
+    /// // エラーを返すタスク（World::try_from）は、エラーの型でもダウンキャストできる
+    /// pw.add("parse_task".to_string(), World::try_from(|| "abc".parse::<u32>())).unwrap();
+    /// pw.exec("parse_task").unwrap();
+    /// match pw.status::<u32>("parse_task") {
+    ///     Err(WorldError::Failed(failure)) => {
+    ///         assert!(failure.downcast_error::<std::num::ParseIntError>().is_some());
+    ///     }
+    ///     other => panic!("unexpected: {:?}", other),
+    /// }
+    ///
+    /// // 期待する型が異なる場合はエラー
+    /// pw.add("typed_task".to_string(), World::from(|| 1u8)).unwrap();
+    /// pw.exec("typed_task").unwrap();
+    /// assert!(matches!(
+    ///     pw.status::<String>("typed_task"),
+    ///     Err(WorldError::DowncastFailed { actual: "u8", .. })
+    /// ));
+    ///
     /// // 存在しないWorldのstatusはエラー
-    /// assert!(pw.status::<()>("non_existent_task").is_err());
-    /// ```
-    pub fn status<T: Send + 'static + std::any::Any>(&self, id: &str) -> Result<T, String> {
-        self.status_any(id)? // まずAnyWorldトレイトオブジェクトとして結果を取得
-            .downcast::<T>() // T型にダウンキャストを試みる
-            .map(|b| *b) // BoxからTを取り出す
-            .map_err(|_| {
-                format!(
-                    "Failed to downcast result for World '{}' to expected type.",
-                    id
-                )
+    /// assert_eq!(
+    ///     pw.status::<()>("non_existent_task"),
+    ///     Err(WorldError::NotFound("non_existent_task".to_string()))
+    /// );
+    /// ```
+    pub fn status<T: Send + 'static + std::any::Any>(&self, id: &str) -> Result<T, WorldError> {
+        let world = self
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))?;
+        // まずAnyWorldトレイトオブジェクトとして結果を取得
+        let result = match self.take_forwarded(id) {
+            Some(result) => result,
+            None => world.any_status()?,
+        };
+        downcast_result(world.as_ref(), result)
+    }
+
+    /// 指定されたWorldの結果が得られると完了する `Future` を返します。
+    ///
+    /// `status` と同じ結果になりますが、スレッドをブロックせずに待機します（`World::join` を参照）。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldError};
+    /// # use std::future::Future;
+    /// # use std::sync::Arc;
+    /// # use std::task::{Context, Poll, Wake};
+    /// # use std::thread::{self, Thread};
+    /// # struct ThreadWaker(Thread);
+    /// # impl Wake for ThreadWaker {
+    /// #     fn wake(self: Arc<Self>) {
+    /// #         self.0.unpark();
+    /// #     }
+    /// # }
+    /// # fn block_on<F: Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let waker = Arc::new(ThreadWaker(thread::current())).into();
+    /// #     let mut cx = Context::from_waker(&waker);
+    /// #     loop {
+    /// #         match future.as_mut().poll(&mut cx) {
+    /// #             Poll::Ready(output) => return output,
+    /// #             Poll::Pending => thread::park(),
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("answer".to_string(), World::from(|| 42)).unwrap();
+    /// pw.start_all();
+    ///
+    /// block_on(async {
+    ///     assert_eq!(pw.status_async::<i32>("answer").await, Ok(42));
+    ///     assert_eq!(
+    ///         pw.status_async::<i32>("missing").await,
+    ///         Err(WorldError::NotFound("missing".to_string()))
+    ///     );
+    /// });
+    /// ```
+    pub fn status_async<T: Send + 'static + std::any::Any>(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<T, WorldError>> + '_ {
+        let id = id.to_string();
+        let world = self.get(&id);
+        let mut waker = None;
+        future::poll_fn(move |cx| {
+            let Some(world) = &world else {
+                return Poll::Ready(Err(WorldError::NotFound(id.clone())));
+            };
+            cancel::poll_completion(
+                cx,
+                &mut waker,
+                || world.any_history().len(),
+                || self.take_result(&id, world.as_ref()),
+                |callback| world.any_on_complete(callback),
+            )
+            .map(|result| result.and_then(|boxed| downcast_result(world.as_ref(), boxed)))
+        })
+    }
+
+    /// 指定されたWorldがパニックで失敗した場合に、`panic!` に渡された元のペイロードを取り出します。
+    ///
+    /// 詳しくは `World::take_panic_payload` を参照してください。
+    ///
+    /// # Errors
+    /// Worldが見つからない場合にエラーを返します。
+    pub fn take_panic_payload(&self, id: &str) -> Result<Option<Box<dyn Any + Send>>, WorldError> {
+        let world = self
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.to_string()))?;
+        Ok(world.any_take_panic_payload())
+    }
+
+    /// 指定されたWorldの結果が既に得られていれば、ブロックせずに期待される型で返します。
+    ///
+    /// # 戻り値
+    /// `None` - Worldがまだ結果を返していない場合。
+    /// `Some(result)` - `status` が返すのと同じ結果。Worldが見つからない場合は`Some(Err(..))`を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("slow".to_string(), World::from(|| { sleep(Duration::from_millis(50)); 1 })).unwrap();
+    /// pw.exec("slow").unwrap();
+    ///
+    /// assert_eq!(pw.try_status::<i32>("slow"), None);
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(pw.try_status::<i32>("slow"), Some(Ok(1)));
+    ///
+    /// assert!(matches!(pw.try_status::<i32>("non_existent_task"), Some(Err(_))));
+    /// ```
+    pub fn try_status<T: Send + 'static + std::any::Any>(
+        &self,
+        id: &str,
+    ) -> Option<Result<T, WorldError>> {
+        let Some(world) = self.get(id) else {
+            return Some(Err(WorldError::NotFound(id.to_string())));
+        };
+        self.take_result(id, world.as_ref())
+            .map(|result| result.and_then(|boxed| downcast_result(world.as_ref(), boxed)))
+    }
+
+    /// 指定されたWorldの結果を最大 `timeout` の間だけ待機し、期待される型で返します。
+    ///
+    /// # 戻り値
+    /// `None` - `timeout` が経過してもWorldが結果を返さなかった場合。
+    /// `Some(result)` - `status` が返すのと同じ結果。Worldが見つからない場合は`Some(Err(..))`を返します。
+    pub fn status_timeout<T: Send + 'static + std::any::Any>(
+        &self,
+        id: &str,
+        timeout: Duration,
+    ) -> Option<Result<T, WorldError>> {
+        let Some(world) = self.get(id) else {
+            return Some(Err(WorldError::NotFound(id.to_string())));
+        };
+        self.take_forwarded(id)
+            .map(Ok)
+            .or_else(|| world.any_status_timeout(timeout))
+            .map(|result| result.and_then(|boxed| downcast_result(world.as_ref(), boxed)))
+    }
+
+    /// 指定されたWorldのうち、最初に結果を返したものを待機し、そのIDと結果を返します。
+    ///
+    /// ポーリングは行わず、Worldの完了通知を待ちます。
+    /// 既に結果を返しているWorldがあれば、すぐにそのWorldの結果が返ります。
+    /// 一度取得した結果は再度取得できないため、続けて呼び出す場合は取得済みのIDを除外してください。
+    ///
+    /// # Errors
+    /// `ids` が空の場合、またはWorldが見つからない場合にエラーを返します。
+    /// 待機中のWorldが `reset`（`restart` を含む）され、待っていた実行の結果が得られなくなった場合は
+    /// `WorldError::WaitAbandoned` を返します。新しい実行を待つ場合は、もう一度呼び出してください。
+    /// 指定したWorldの結果がすべて他の呼び出しで取得された場合は `WorldError::ResultAlreadyTaken` を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("slow".to_string(), World::from(|| { sleep(Duration::from_millis(200)); 1 })).unwrap();
+    /// pw.add("fast".to_string(), World::from(|| { sleep(Duration::from_millis(10)); 2 })).unwrap();
+    /// pw.start_all();
+    ///
+    /// let (id, result) = pw.wait_any(&["slow", "fast"]).unwrap();
+    /// assert_eq!(id, "fast");
+    /// assert_eq!(*result.unwrap().downcast::<i32>().unwrap(), 2);
+    /// ```
+    pub fn wait_any(&self, ids: &[&str]) -> Result<(String, WorldResult), WorldError> {
+        if ids.is_empty() {
+            return Err(WorldError::NoWorlds);
+        }
+        let worlds = self.get_all(ids)?;
+        let completed = Self::subscribe_completion(&worlds);
+        loop {
+            let (index, result) = self.recv_completed(&completed, &worlds)?;
+            if let Some(result) = result {
+                return Ok((worlds[index].0.clone(), result));
+            }
+        }
+    }
+
+    /// 指定されたすべてのWorldの結果を待機し、完了した順にIDと結果を返します。
+    ///
+    /// ポーリングは行わず、Worldの完了通知を待ちます。
+    ///
+    /// # Errors
+    /// Worldが見つからない場合にエラーを返します。
+    /// 待機中のWorldが `reset`（`restart` を含む）され、待っていた実行の結果が得られなくなった場合は
+    /// `WorldError::WaitAbandoned` を、結果が他の呼び出しで取得された場合は
+    /// `WorldError::ResultAlreadyTaken` を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("second".to_string(), World::from(|| { sleep(Duration::from_millis(100)); 2 })).unwrap();
+    /// pw.add("first".to_string(), World::from(|| { sleep(Duration::from_millis(10)); "one" })).unwrap();
+    /// pw.start_all();
+    ///
+    /// let results = pw.wait_all(&["second", "first"]).unwrap();
+    /// let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+    /// assert_eq!(ids, vec!["first", "second"]); // 完了した順
+    /// ```
+    ///
+    /// 待機中に再起動されたWorldは、待っていた実行の結果を返せません。
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldError};
+    /// use std::sync::Arc;
+    /// use std::thread::{sleep, spawn};
+    /// use std::time::Duration;
+    ///
+    /// let pw = Arc::new(Multiverse::new());
+    /// pw.add("job".to_string(), World::repeatable(|| { sleep(Duration::from_millis(200)); 1 })).unwrap();
+    /// pw.start_all();
+    ///
+    /// let waiter = {
+    ///     let pw = Arc::clone(&pw);
+    ///     spawn(move || pw.wait_all(&["job"]).map(|results| results.len()))
+    /// };
+    /// sleep(Duration::from_millis(50));
+    /// pw.stop_all();
+    /// pw.restart("job").unwrap();
+    /// assert_eq!(waiter.join().unwrap(), Err(WorldError::WaitAbandoned("job".to_string())));
+    ///
+    /// // 新しい実行の結果は、もう一度待機すれば受け取れる
+    /// assert_eq!(pw.wait_all(&["job"]).unwrap().len(), 1);
+    /// ```
+    pub fn wait_all(&self, ids: &[&str]) -> Result<Vec<(String, WorldResult)>, WorldError> {
+        let worlds = self.get_all(ids)?;
+        let completed = Self::subscribe_completion(&worlds);
+        let mut results = Vec::with_capacity(worlds.len());
+        while results.len() < worlds.len() {
+            let (index, result) = self.recv_completed(&completed, &worlds)?;
+            if let Some(result) = result {
+                results.push((worlds[index].0.clone(), result));
+            }
+        }
+        Ok(results)
+    }
+
+    /// 下流のWorldに渡すために取り出されていた結果があれば、それを取り出します。
+    fn take_forwarded(&self, id: &str) -> Option<Box<dyn Any + Send>> {
+        self.forwarded.lock().unwrap().remove(id)
+    }
+
+    /// 結果が既に得られていれば、ブロックせずに返します（下流に渡すために取り出されていた結果を含みます）。
+    fn take_result(&self, id: &str, world: &dyn AnyWorld) -> Option<WorldResult> {
+        self.take_forwarded(id)
+            .map(Ok)
+            .or_else(|| world.any_try_status())
+    }
+
+    /// 指定されたIDのWorldをまとめて取得します。見つからないIDがあればエラーを返します。
+    fn get_all(&self, ids: &[&str]) -> Result<Vec<WorldEntry>, WorldError> {
+        ids.iter()
+            .map(|id| {
+                self.get(id)
+                    .map(|world| (id.to_string(), world))
+                    .ok_or_else(|| WorldError::NotFound(id.to_string()))
             })
+            .collect()
+    }
+
+    /// 各Worldの完了時に、そのインデックスが送信されるチャネルを作成します。
+    ///
+    /// 完了する前に `reset` で実行が置き換えられ、コールバックが呼ばれずに破棄された場合は
+    /// `Completion::Abandoned` が送信されます。
+    fn subscribe_completion(worlds: &[WorldEntry]) -> mpsc::Receiver<Completion> {
+        let (tx, rx) = mpsc::channel();
+        for (index, (_, world)) in worlds.iter().enumerate() {
+            let notifier = CompletionNotifier {
+                index,
+                sender: Some(tx.clone()),
+            };
+            world.any_on_complete(Box::new(move || notifier.fire()));
+        }
+        rx
+    }
+
+    /// 完了通知を1つ受け取り、そのWorldのインデックスと、得られた結果を返します。
+    ///
+    /// 結果が他の呼び出しで取得されていた場合は `None` を返します。
+    fn recv_completed(
+        &self,
+        completed: &mpsc::Receiver<Completion>,
+        worlds: &[WorldEntry],
+    ) -> Result<(usize, Option<WorldResult>), WorldError> {
+        // 送信側は各Worldのコールバックが保持しているため、全Worldの通知が済むまで切断されない
+        let index = match completed.recv() {
+            Ok(Completion::Fired(index)) => index,
+            Ok(Completion::Abandoned(index)) => {
+                return Err(WorldError::WaitAbandoned(worlds[index].0.clone()));
+            }
+            Err(_) => return Err(WorldError::ResultAlreadyTaken),
+        };
+        let (id, world) = &worlds[index];
+        if let Some(result) = self.take_result(id, world.as_ref()) {
+            return Ok((index, Some(result)));
+        }
+        // 完了通知の後に reset された場合、Worldは既に新しい実行に移っている
+        let status = world.any_progress();
+        if status == WorldStatus::Ready || status.is_active() {
+            return Err(WorldError::WaitAbandoned(id.clone()));
+        }
+        Ok((index, None))
+    }
+}
+
+/// `wait_any` / `wait_all` が返す、型消去されたWorldの結果です。
+pub type WorldResult = Result<Box<dyn Any + Send>, WorldError>;
+
+/// IDとWorldの組
+type WorldEntry = (String, Arc<dyn AnyWorld>);
+
+/// `wait_any` / `wait_all` が受け取る、Worldの完了通知です。
+enum Completion {
+    /// Worldが結果を受け取れる状態になった
+    Fired(usize),
+    /// 完了する前に実行が置き換えられ、通知が届かなくなった
+    Abandoned(usize),
+}
+
+/// 完了時のコールバックに渡され、呼ばれずに破棄されたときにも通知を送ります。
+struct CompletionNotifier {
+    index: usize,
+    sender: Option<mpsc::Sender<Completion>>,
+}
+
+impl CompletionNotifier {
+    fn fire(mut self) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(Completion::Fired(self.index));
+        }
+    }
+}
+
+impl Drop for CompletionNotifier {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(Completion::Abandoned(self.index));
+        }
+    }
+}
+
+/// 型消去された結果を、期待される型 `T` にダウンキャストします。
+fn downcast_result<T: 'static>(
+    world: &dyn AnyWorld,
+    result: Box<dyn Any + Send>,
+) -> Result<T, WorldError> {
+    result
+        .downcast::<T>() // T型にダウンキャストを試みる
+        .map(|b| *b) // BoxからTを取り出す
+        .map_err(|_| WorldError::DowncastFailed {
+            expected: std::any::type_name::<T>(),
+            actual: world.result_type_name(),
+        })
+}
+
+/// 状態の変化を購読者に送ります。購読を解除した（レシーバーが破棄された）購読者は取り除きます。
+fn publish(
+    subscribers: &Mutex<Vec<EventCallback>>,
+    id: &str,
+    from: &WorldStatus,
+    to: &WorldStatus,
+) {
+    let mut subscribers = subscribers.lock().unwrap();
+    if subscribers.is_empty() {
+        return;
     }
+    let event = WorldEvent {
+        id: id.to_string(),
+        from: from.clone(),
+        to: to.clone(),
+        at: Instant::now(),
+    };
+    subscribers.retain(|subscriber| subscriber(&event));
 }
 
 impl Default for Multiverse {
@@ -0,0 +1,205 @@
+use crate::failure::Failure;
+use crate::logs::{LogStream, SharedLogBuffer};
+use crate::world::World;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 子プロセスの終了をポーリングする間隔
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 停止されたプロセスの出力の読み取りを待つ最大時間
+const READER_WAIT: Duration = Duration::from_millis(100);
+
+/// `kill` でSIGTERMを送ってからSIGKILLを送るまでのデフォルトの猶予期間
+pub(crate) const DEFAULT_KILL_GRACE: Duration = Duration::from_secs(5);
+
+/// プロセスベースのWorldが実行中の子プロセスを共有するためのスロット。
+/// `None` は子プロセスがまだ起動していないことを表します。
+pub(crate) type ChildSlot = Arc<Mutex<Option<Child>>>;
+
+impl World<Output> {
+    /// 外部コマンドを子プロセスとして実行するWorldを作成します。
+    ///
+    /// スレッドで実行されるWorldと異なり、プロセスベースのWorldは `kill()` によって
+    /// 本当に強制終了できます。`kill()` はまずSIGTERMを送り、猶予期間
+    /// （`with_kill_grace` で変更可能、デフォルトは5秒）内に終了しなければSIGKILLを送って
+    /// 子プロセスを回収し、状態を `WorldStatus::Killed` に遷移させます。
+    /// `stop()` はSIGTERMのみを送り、状態を `WorldStatus::Stopped` にします。
+    ///
+    /// 子プロセスの標準出力と標準エラー出力は捕捉され、結果の `Output` に格納されます。
+    /// 同時に1行ずつWorldのログにも記録されるため、実行中や失敗した後でも `logs()` / `follow_logs()` で確認できます。
+    /// コマンドの起動に失敗した場合、Worldは `WorldStatus::Failed` になり、
+    /// 起動時の `std::io::Error` を `Failure::downcast_error` で取り出せます。
+    ///
+    /// # 引数
+    /// * `cmd` - 実行するコマンド。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldError, WorldStatus};
+    /// use std::process::Command;
+    ///
+    /// let mut cmd = Command::new("echo");
+    /// cmd.arg("hello");
+    /// let world = World::in_process(cmd);
+    ///
+    /// let output = world.run().unwrap();
+    /// assert!(output.status.success());
+    /// assert_eq!(String::from_utf8_lossy(&output.stdout), "hello\n");
+    /// assert_eq!(world.progress(), WorldStatus::Finished);
+    ///
+    /// // 起動できないコマンドは失敗になる
+    /// let world = World::in_process(Command::new("parallel-world-no-such-command"));
+    /// let Err(WorldError::Failed(failure)) = world.run() else { panic!() };
+    /// let error = failure.downcast_error::<std::io::Error>().unwrap();
+    /// assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+    /// assert!(matches!(world.progress(), WorldStatus::Failed(_)));
+    /// ```
+    pub fn in_process(mut cmd: Command) -> Self {
+        let slot: ChildSlot = Arc::new(Mutex::new(None));
+        let slot_clone = Arc::clone(&slot);
+        let mut world = World::with_job(Some(Box::new(move |ctx| {
+            let token = &ctx.cancel_token;
+            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+            let mut child = cmd.spawn().map_err(Failure::from_error)?;
+
+            // パイプが詰まらないよう、出力は別スレッドで読み続ける
+            let stdout_reader = spawn_reader(child.stdout.take(), &ctx.logs, LogStream::Stdout);
+            let stderr_reader = spawn_reader(child.stderr.take(), &ctx.logs, LogStream::Stderr);
+
+            *slot_clone.lock().unwrap() = Some(child);
+            // stop() によるキャンセルはSIGTERMとして子プロセスに伝える
+            let term_slot = Arc::clone(&slot_clone);
+            token.on_cancel(move || {
+                if let Some(child) = term_slot.lock().unwrap().as_mut() {
+                    terminate(child);
+                }
+            });
+
+            let status = loop {
+                let exited = slot_clone
+                    .lock()
+                    .unwrap()
+                    .as_mut()
+                    .map(|child| child.try_wait())
+                    .transpose()
+                    .unwrap_or_else(|e| panic!("Failed to wait for process: {}", e))
+                    .flatten();
+                if let Some(status) = exited {
+                    break status;
+                }
+                thread::sleep(POLL_INTERVAL);
+            };
+
+            // 停止・強制終了された場合、孫プロセスがパイプを開いたままにしていることがあるため、
+            // 出力の読み取りは一定時間だけ待つ
+            let cancelled = token.is_cancelled();
+            Ok(Output {
+                status,
+                stdout: collect_output(stdout_reader, cancelled),
+                stderr: collect_output(stderr_reader, cancelled),
+            })
+        })));
+        world.child = Some(slot);
+        world
+    }
+}
+
+/// パイプの内容を最後まで読み取るスレッドを起動します。読み取った内容は1行ずつ `logs` にも記録します。
+fn spawn_reader<P: Read + Send + 'static>(
+    pipe: Option<P>,
+    logs: &SharedLogBuffer,
+    stream: LogStream,
+) -> thread::JoinHandle<Vec<u8>> {
+    let logs = Arc::clone(logs);
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let Some(pipe) = pipe else {
+            return buf;
+        };
+        let mut reader = BufReader::new(pipe);
+        let mut line = Vec::new();
+        while let Ok(n) = reader.read_until(b'\n', &mut line) {
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&line);
+            let text = String::from_utf8_lossy(&line);
+            let text = text.trim_end_matches(['\n', '\r']).to_string();
+            logs.lock().unwrap().push(stream, text);
+            line.clear();
+        }
+        buf
+    })
+}
+
+/// 読み取りスレッドの結果を回収します。
+/// `bounded` が `true` の場合、読み取りが `READER_WAIT` 以内に終わらなければ空の出力を返します。
+fn collect_output(reader: thread::JoinHandle<Vec<u8>>, bounded: bool) -> Vec<u8> {
+    if bounded {
+        let deadline = Instant::now() + READER_WAIT;
+        while !reader.is_finished() {
+            if Instant::now() >= deadline {
+                return Vec::new();
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+    reader.join().unwrap_or_default()
+}
+
+/// 子プロセスに終了要求（UnixではSIGTERM）を送ります。既に終了している場合は何もしません。
+pub(crate) fn terminate(child: &mut Child) {
+    if let Ok(None) = child.try_wait() {
+        send_sigterm(child);
+    }
+}
+
+#[cfg(unix)]
+fn send_sigterm(child: &mut Child) {
+    const SIGTERM: i32 = 15;
+    unsafe extern "C" {
+        fn kill(pid: i32, sig: i32) -> i32;
+    }
+    // try_wait で回収されていないことを確認済みなので、PIDは再利用されていない
+    unsafe {
+        kill(child.id() as i32, SIGTERM);
+    }
+}
+
+#[cfg(not(unix))]
+fn send_sigterm(child: &mut Child) {
+    // SIGTERMに相当する仕組みがないため、即座に強制終了する
+    let _ = child.kill();
+}
+
+/// 子プロセスを強制終了します。
+///
+/// まず終了要求を送り、`grace` の間に終了しなければ `Child::kill`（UnixではSIGKILL）で強制終了して回収します。
+/// このメソッドは子プロセスが回収されるまでブロックします。
+pub(crate) fn kill_with_grace(slot: &ChildSlot, grace: Duration) {
+    if let Some(child) = slot.lock().unwrap().as_mut() {
+        terminate(child);
+    }
+    let deadline = Instant::now() + grace;
+    loop {
+        let mut guard = slot.lock().unwrap();
+        let Some(child) = guard.as_mut() else {
+            // 子プロセスがまだ起動していない場合は、起動を待たずに終了する
+            return;
+        };
+        if !matches!(child.try_wait(), Ok(None)) {
+            return;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        }
+        drop(guard);
+        thread::sleep(POLL_INTERVAL);
+    }
+}
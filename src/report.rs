@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex};
+
+/// # Report
+///
+/// 実行中のWorldが `Reporter` を通じて報告した進捗です（`World::report` / `Multiverse::reports` で取得できます）。
+///
+/// 報告されていない項目は `None` です。
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    /// 進捗の割合（0.0〜1.0）
+    pub fraction: Option<f64>,
+    /// 処理済みの数と全体の数（例: 1000件中420件目なら `(420, 1000)`）
+    pub step: Option<(u64, u64)>,
+    /// 自由形式のメッセージ
+    pub message: Option<String>,
+}
+
+/// # Reporter
+///
+/// `World::with_reporter` で作成されたWorldのクロージャが受け取る、進捗を報告するためのハンドルです。
+///
+/// `Clone` で複製でき、複製はすべて同じ報告先を共有します。
+#[derive(Debug, Clone, Default)]
+pub struct Reporter {
+    report: Arc<Mutex<Report>>,
+}
+
+impl Reporter {
+    pub(crate) fn new(report: Arc<Mutex<Report>>) -> Self {
+        Reporter { report }
+    }
+
+    /// 進捗の割合を報告します。値は0.0〜1.0の範囲に丸められます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::report::Reporter;
+    ///
+    /// let reporter = Reporter::default();
+    /// reporter.set_fraction(1.5);
+    /// assert_eq!(reporter.report().fraction, Some(1.0));
+    /// ```
+    pub fn set_fraction(&self, fraction: f64) {
+        self.report.lock().unwrap().fraction = Some(fraction.clamp(0.0, 1.0));
+    }
+
+    /// 処理済みの数と全体の数を報告します。
+    ///
+    /// `total` が0でなければ、進捗の割合も `current / total` に更新されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::report::Reporter;
+    ///
+    /// let reporter = Reporter::default();
+    /// reporter.set_step(420, 1000);
+    /// assert_eq!(reporter.report().step, Some((420, 1000)));
+    /// assert_eq!(reporter.report().fraction, Some(0.42));
+    /// ```
+    pub fn set_step(&self, current: u64, total: u64) {
+        let mut report = self.report.lock().unwrap();
+        report.step = Some((current, total));
+        if total > 0 {
+            report.fraction = Some((current as f64 / total as f64).clamp(0.0, 1.0));
+        }
+    }
+
+    /// 自由形式のメッセージを報告します。
+    pub fn set_message(&self, message: impl Into<String>) {
+        self.report.lock().unwrap().message = Some(message.into());
+    }
+
+    /// これまでに報告した進捗を取得します。
+    pub fn report(&self) -> Report {
+        self.report.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::failure::Failure;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 再試行までの待ち時間の決め方を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    /// 待たずにすぐ再試行します。
+    Immediate,
+    /// 毎回同じ時間だけ待ちます。
+    Fixed(Duration),
+    /// `initial` から始めて、再試行のたびに待ち時間を2倍にします（`max` を上限とします）。
+    Exponential {
+        /// 最初の再試行までの待ち時間
+        initial: Duration,
+        /// 待ち時間の上限
+        max: Duration,
+    },
+    /// `Exponential` と同様に上限を2倍ずつ増やし、0からその上限までのランダムな時間だけ待ちます（フルジッター）。
+    /// 多数のWorldが同時に再試行して負荷が集中するのを防ぎます。
+    Jittered {
+        /// 最初の再試行までの待ち時間の上限
+        initial: Duration,
+        /// 待ち時間の上限
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// `retry` 回目の再試行（1から始まる）までの待ち時間を計算します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::Backoff;
+    /// use std::time::Duration;
+    ///
+    /// let backoff = Backoff::Exponential {
+    ///     initial: Duration::from_millis(100),
+    ///     max: Duration::from_millis(500),
+    /// };
+    /// assert_eq!(backoff.delay(1), Duration::from_millis(100));
+    /// assert_eq!(backoff.delay(2), Duration::from_millis(200));
+    /// assert_eq!(backoff.delay(3), Duration::from_millis(400));
+    /// assert_eq!(backoff.delay(4), Duration::from_millis(500)); // 上限で頭打ち
+    /// ```
+    pub fn delay(&self, retry: u32) -> Duration {
+        match *self {
+            Backoff::Immediate => Duration::ZERO,
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max } => exponential(initial, max, retry),
+            Backoff::Jittered { initial, max } => {
+                let ceiling = exponential(initial, max, retry);
+                ceiling.mul_f64(random_fraction())
+            }
+        }
+    }
+}
+
+/// `initial * 2^(retry - 1)` を `max` で頭打ちにした値を返します。
+fn exponential(initial: Duration, max: Duration, retry: u32) -> Duration {
+    let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+    initial.saturating_mul(factor).min(max)
+}
+
+/// 0.0以上1.0未満の乱数を返します（ジッター用のため、暗号学的な強度は不要です）。
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// 失敗を再試行の対象とするかどうかを判定する関数
+type RetryPredicate = Arc<dyn Fn(&Failure) -> bool + Send + Sync + 'static>;
+
+/// # RetryPolicy
+///
+/// `World::with_retry` で使用する再試行ポリシーです。
+///
+/// 最大試行回数、再試行までの待ち時間（`Backoff`）、および再試行の対象とする失敗の条件を指定します。
+///
+/// # 例
+/// ```
+/// use parallel_world::{Backoff, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new(5)
+///     .backoff(Backoff::Jittered {
+///         initial: Duration::from_millis(10),
+///         max: Duration::from_secs(1),
+///     })
+///     // パニックは再試行せず、返されたエラーだけを再試行する
+///     .retry_if(|failure| failure.is_error());
+/// assert_eq!(policy.max_attempts(), 5);
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Backoff,
+    predicate: Option<RetryPredicate>,
+}
+
+impl RetryPolicy {
+    /// 最大 `max_attempts` 回（初回を含む）まで試行するポリシーを作成します。
+    ///
+    /// 待ち時間は `Backoff::Immediate`、すべての失敗（パニックと返されたエラー）が再試行の対象です。
+    /// `max_attempts` が0の場合は1として扱います。
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            backoff: Backoff::Immediate,
+            predicate: None,
+        }
+    }
+
+    /// 再試行までの待ち時間の決め方を設定します。
+    pub fn backoff(self, backoff: Backoff) -> Self {
+        RetryPolicy { backoff, ..self }
+    }
+
+    /// 再試行の対象とする失敗の条件を設定します。`predicate` が `false` を返した失敗は、すぐにWorldの失敗となります。
+    pub fn retry_if<P>(self, predicate: P) -> Self
+    where
+        P: Fn(&Failure) -> bool + Send + Sync + 'static,
+    {
+        RetryPolicy {
+            predicate: Some(Arc::new(predicate)),
+            ..self
+        }
+    }
+
+    /// 最大試行回数（初回を含む）を返します。
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// `attempt` 回目（1から始まる）の試行が `failure` で失敗したときに、再試行するまでの待ち時間を返します。
+    /// 再試行しない場合は `None` を返します。
+    pub(crate) fn next_delay(&self, attempt: u32, failure: &Failure) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        if let Some(predicate) = &self.predicate
+            && !predicate(failure)
+        {
+            return None;
+        }
+        Some(self.backoff.delay(attempt))
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("has_predicate", &self.predicate.is_some())
+            .finish()
+    }
+}
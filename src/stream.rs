@@ -0,0 +1,313 @@
+use crate::error::WorldError;
+use crate::world::World;
+use std::any::{Any, TypeId};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// ストリームを消費する `Stream` を、取り出されるまで型を消して保持するスロット
+pub(crate) struct StreamSlot {
+    consumer: Mutex<Option<Box<dyn Any + Send>>>,
+    item_type: TypeId,
+    item_type_name: &'static str,
+}
+
+/// `Emitter` から `Stream` へアイテムを渡す、容量付きのキュー
+struct Channel<T> {
+    state: Mutex<ChannelState<T>>,
+    /// アイテムが追加・取り出された、または閉じられたことを知らせる
+    changed: Condvar,
+    capacity: usize,
+}
+
+struct ChannelState<T> {
+    items: VecDeque<T>,
+    /// Worldが完了または停止され、これ以上アイテムが追加されない
+    closed: bool,
+    /// `Stream` が破棄され、追加されたアイテムを受け取る側がいない
+    disconnected: bool,
+}
+
+impl<T> Channel<T> {
+    /// これ以上アイテムを追加できないようにします。キューに残っているアイテムは受け取れます。
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.changed.notify_all();
+    }
+}
+
+/// # Emitter
+///
+/// `World::with_emitter` で作成されたWorldのクロージャが受け取る、アイテムを送り出すためのハンドルです。
+///
+/// `Clone` で複製でき、複製はすべて同じストリームにアイテムを送ります。
+pub struct Emitter<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Clone for Emitter<T> {
+    fn clone(&self) -> Self {
+        Emitter {
+            channel: Arc::clone(&self.channel),
+        }
+    }
+}
+
+impl<T> Emitter<T> {
+    /// アイテムをストリームに送ります。
+    ///
+    /// バッファが容量いっぱいであれば、消費側が取り出して空きができるまでブロックします（背圧）。
+    /// Worldが停止されたか、消費側の `Stream` が破棄されていれば、アイテムを捨てて `false` を返します。
+    /// `false` が返されたら、それ以上送っても受け取られないため、クロージャは速やかに終了してください。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldStatus};
+    ///
+    /// let world = World::with_emitter(1, |emitter| {
+    ///     let mut n: u32 = 0;
+    ///     while emitter.emit(n) {
+    ///         n += 1;
+    ///     }
+    ///     n
+    /// });
+    /// world.start().unwrap();
+    ///
+    /// let mut stream = world.stream::<u32>().unwrap();
+    /// assert_eq!(stream.next(), Some(0));
+    /// // 停止されると、バッファの空きを待っていた emit が false を返す
+    /// world.stop().unwrap();
+    /// assert!(stream.count() <= 1);
+    /// assert_eq!(world.progress(), WorldStatus::Stopped);
+    /// ```
+    pub fn emit(&self, item: T) -> bool {
+        let mut state = self.channel.state.lock().unwrap();
+        loop {
+            if state.closed || state.disconnected {
+                return false;
+            }
+            if state.items.len() < self.channel.capacity {
+                state.items.push_back(item);
+                self.channel.changed.notify_all();
+                return true;
+            }
+            state = self.channel.changed.wait(state).unwrap();
+        }
+    }
+}
+
+/// # Stream
+///
+/// `World::with_emitter` で作成されたWorldが送り出したアイテムを、送られた順に受け取るイテレーターです
+/// （`World::stream` / `Multiverse::stream` で取得できます）。
+///
+/// 次のアイテムが送られるまでブロックし、Worldが完了（停止などを含む）して残りのアイテムを受け取り終えると終わります。
+/// 破棄すると、それ以降の `Emitter::emit` は `false` を返します。
+pub struct Stream<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Iterator for Stream<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut state = self.channel.state.lock().unwrap();
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                self.channel.changed.notify_all();
+                return Some(item);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.channel.changed.wait(state).unwrap();
+        }
+    }
+}
+
+impl<T> Drop for Stream<T> {
+    fn drop(&mut self) {
+        let mut state = self.channel.state.lock().unwrap();
+        state.disconnected = true;
+        state.items.clear();
+        self.channel.changed.notify_all();
+    }
+}
+
+impl<R: Send + 'static> World<R> {
+    /// 複数のアイテムを順に送り出す関数からWorldを作成します。
+    ///
+    /// クロージャには `Emitter` が渡され、`emit` で何度でもアイテムを送り出せます。
+    /// 送り出したアイテムは `stream()` や `Multiverse::stream` で取得した `Stream` で受け取り、
+    /// クロージャの戻り値は、すべて送り終えた後の要約として通常の結果（`status()` など）で受け取ります。
+    ///
+    /// 受け取られていないアイテムは最大 `capacity` 個（0の場合は1個）までバッファに保持され、
+    /// バッファがいっぱいの間は `emit` がブロックします。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    ///
+    /// let world = World::with_emitter(4, |emitter| {
+    ///     let mut sent = 0;
+    ///     for line in ["GET /", "GET /about", "POST /login"] {
+    ///         if !emitter.emit(line.to_string()) {
+    ///             break;
+    ///         }
+    ///         sent += 1;
+    ///     }
+    ///     sent
+    /// });
+    /// world.start().unwrap();
+    ///
+    /// let lines: Vec<String> = world.stream().unwrap().collect();
+    /// assert_eq!(lines, ["GET /", "GET /about", "POST /login"]);
+    /// assert_eq!(world.status(), Ok(3));
+    /// ```
+    pub fn with_emitter<T, F>(capacity: usize, f: F) -> Self
+    where
+        T: Send + 'static,
+        F: FnOnce(Emitter<T>) -> R + Send + 'static,
+    {
+        let channel = Arc::new(Channel {
+            state: Mutex::new(ChannelState {
+                items: VecDeque::new(),
+                closed: false,
+                disconnected: false,
+            }),
+            changed: Condvar::new(),
+            capacity: capacity.max(1),
+        });
+        let emitter = Emitter {
+            channel: Arc::clone(&channel),
+        };
+        let mut world = World::with_job(Some(Box::new(move |ctx| {
+            // 停止されたら、emit で待っているクロージャを起こす
+            let cancelled = Arc::clone(&emitter.channel);
+            ctx.cancel_token.on_cancel(move || cancelled.close());
+            Ok(f(emitter))
+        })));
+
+        // 完了したら（実行されずに停止された場合も）、ストリームを終わらせる
+        let completed = Arc::clone(&channel);
+        world.on_complete(move || completed.close());
+        world.stream = Some(StreamSlot {
+            consumer: Mutex::new(Some(Box::new(Stream { channel }))),
+            item_type: TypeId::of::<T>(),
+            item_type_name: std::any::type_name::<T>(),
+        });
+        world
+    }
+
+    /// `with_emitter` で作成されたWorldが送り出すアイテムを受け取る `Stream` を取り出します。
+    ///
+    /// `Stream` を取り出せるのは一度だけです。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NoStream)` - `with_emitter` で作成されたWorldではない場合に返されます。
+    /// * `Err(WorldError::StreamAlreadyTaken)` - `Stream` が既に取り出されている場合に返されます。
+    /// * `Err(WorldError::DowncastFailed)` - `T` が送り出されるアイテムの型と異なる場合に返されます。
+    pub fn stream<T: Send + 'static>(&self) -> Result<Stream<T>, WorldError> {
+        let consumer = self.take_stream(TypeId::of::<T>(), std::any::type_name::<T>())?;
+        Ok(*consumer
+            .downcast::<Stream<T>>()
+            .expect("item type was checked"))
+    }
+
+    /// アイテムの型が `item_type` であることを確認して、型を消した `Stream` を取り出します。
+    pub(crate) fn take_stream(
+        &self,
+        item_type: TypeId,
+        item_type_name: &'static str,
+    ) -> Result<Box<dyn Any + Send>, WorldError> {
+        let slot = self.stream.as_ref().ok_or(WorldError::NoStream)?;
+        if slot.item_type != item_type {
+            return Err(WorldError::DowncastFailed {
+                expected: item_type_name,
+                actual: slot.item_type_name,
+            });
+        }
+        slot.consumer
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or(WorldError::StreamAlreadyTaken)
+    }
+}
+
+/// # StreamWorld
+///
+/// アイテムの型 `T` を型引数に持つ、`World::with_emitter` で作成されたWorldです。
+///
+/// アイテムの型が決まっているため、`stream()` で型を指定せずに `Stream<T>` を取り出せます。
+/// `R` はクロージャが最後に返す要約の型で、省略すると `()` になります。
+/// それ以外の操作は `World<R>` と同じで、`Multiverse` に追加するときは `into_world()` で `World<R>` に変換します。
+///
+/// # 例
+/// ```
+/// use parallel_world::{Multiverse, StreamWorld};
+///
+/// let poller = StreamWorld::new(8, |emitter| {
+///     for reading in [21.5, 21.7, 22.0] {
+///         emitter.emit(reading);
+///     }
+///     "sensor-1"
+/// });
+/// poller.start().unwrap();
+/// let readings: Vec<f64> = poller.stream().unwrap().collect();
+/// assert_eq!(readings, [21.5, 21.7, 22.0]);
+/// assert_eq!(poller.status(), Ok("sensor-1"));
+///
+/// // Multiverseでは、通常のWorldと同じくIDで管理する
+/// let mv = Multiverse::new();
+/// let ticks: StreamWorld<u32> = StreamWorld::new(1, |emitter| {
+///     (1..=3).for_each(|n| {
+///         emitter.emit(n);
+///     })
+/// });
+/// mv.add("ticks".to_string(), ticks.into_world()).unwrap();
+/// mv.start_all();
+/// assert_eq!(mv.stream::<u32>("ticks").unwrap().sum::<u32>(), 6);
+/// ```
+pub struct StreamWorld<T, R: Send + 'static = ()> {
+    world: World<R>,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + 'static, R: Send + 'static> StreamWorld<T, R> {
+    /// 複数のアイテムを順に送り出す関数から `StreamWorld` を作成します。
+    ///
+    /// `World::with_emitter` と同じく、受け取られていないアイテムは最大 `capacity` 個（0の場合は1個）まで
+    /// バッファに保持され、バッファがいっぱいの間は `emit` がブロックします。
+    pub fn new<F>(capacity: usize, f: F) -> Self
+    where
+        F: FnOnce(Emitter<T>) -> R + Send + 'static,
+    {
+        StreamWorld {
+            world: World::with_emitter(capacity, f),
+            item: PhantomData,
+        }
+    }
+
+    /// 送り出されるアイテムを受け取る `Stream` を取り出します。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::StreamAlreadyTaken)` - `Stream` が既に取り出されている場合に返されます。
+    pub fn stream(&self) -> Result<Stream<T>, WorldError> {
+        self.world.stream()
+    }
+
+    /// `World<R>` に変換します。アイテムは `World::stream::<T>` や `Multiverse::stream::<T>` で受け取れます。
+    pub fn into_world(self) -> World<R> {
+        self.world
+    }
+}
+
+impl<T, R: Send + 'static> Deref for StreamWorld<T, R> {
+    type Target = World<R>;
+
+    fn deref(&self) -> &World<R> {
+        &self.world
+    }
+}
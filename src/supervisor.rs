@@ -0,0 +1,314 @@
+use crate::cancel::CancelToken;
+use crate::error::WorldError;
+use crate::failure::Failure;
+use crate::parallel_worlds::Multiverse;
+use crate::world::{AnyWorld, World, WorldStatus};
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::{Arc, Weak, mpsc};
+use std::time::{Duration, Instant};
+
+/// 再起動の許容回数の既定値
+const DEFAULT_MAX_RESTARTS: u32 = 3;
+/// 再起動の回数を数える期間の既定値
+const DEFAULT_PERIOD: Duration = Duration::from_secs(5);
+
+/// 子Worldが異常終了したときに、どの子Worldを再起動するかを表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// 異常終了した子Worldだけを再起動します（one_for_one）。
+    OneForOne,
+    /// 他の子Worldもすべて停止し、すべての子Worldを再起動します（one_for_all）。
+    OneForAll,
+    /// 異常終了した子Worldと、それより後に登録された子Worldを停止・再起動します（rest_for_one）。
+    RestForOne,
+}
+
+/// # Supervisor
+///
+/// `Multiverse` に登録されたWorldを監視し、異常終了（`WorldStatus::Failed` / `WorldStatus::TimedOut`）したときに
+/// `RestartStrategy` に従って再起動する、Erlang風のスーパーバイザーです。
+///
+/// 子Worldは `World::repeatable` で作成され、監視を開始する前に `Multiverse` に追加されている必要があります。
+/// 正常に完了したり、外部から停止・強制終了されたりした子Worldは再起動しません。
+///
+/// `period` の間に `max_restarts` 回を超えて再起動が必要になると、スーパーバイザーはすべての子Worldを停止して
+/// 監視を断念し、`WorldError::RestartLimitExceeded` で失敗します。
+/// スーパーバイザー自身も `into_world` でWorldになるため、親のスーパーバイザーの子として登録すれば、
+/// 断念したスーパーバイザーは親によって再起動されます（エスカレーション）。
+///
+/// スーパーバイザーは `Multiverse` を弱い参照で保持するため、`into_world` で作成したWorldを
+/// 同じ `Multiverse` に登録しても循環参照にはなりません。
+/// 監視中に `Multiverse` が破棄された場合は、`WorldError::NotFound` で失敗します。
+///
+/// # 例
+/// ```
+/// use parallel_world::{Multiverse, RestartStrategy, Supervisor, World};
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// let mv = Arc::new(Multiverse::new());
+/// let runs = Arc::new(AtomicU32::new(0));
+/// let runs_clone = Arc::clone(&runs);
+/// mv.add("worker".to_string(), World::repeatable(move || {
+///     // 最初の実行だけパニックする
+///     if runs_clone.fetch_add(1, Ordering::SeqCst) == 0 {
+///         panic!("worker crashed");
+///     }
+///     "recovered"
+/// })).unwrap();
+///
+/// let supervisor = Supervisor::new(Arc::clone(&mv), RestartStrategy::OneForOne)
+///     .child("worker")
+///     .into_world();
+/// supervisor.start().unwrap();
+///
+/// // パニックしたWorldが再起動されるのを待つ
+/// while mv.history("worker").unwrap().is_empty() {
+///     sleep(Duration::from_millis(5));
+/// }
+/// assert_eq!(mv.status::<&str>("worker"), Ok("recovered"));
+/// assert_eq!(runs.load(Ordering::SeqCst), 2);
+/// supervisor.stop().unwrap();
+/// ```
+pub struct Supervisor {
+    /// 子Worldが登録されている `Multiverse`（監視するWorldに所有されるため、弱い参照で保持する）
+    multiverse: Weak<Multiverse>,
+    /// 再起動の戦略
+    strategy: RestartStrategy,
+    /// `period` の間に許容する再起動の回数
+    max_restarts: u32,
+    /// 再起動の回数を数える期間
+    period: Duration,
+    /// 監視する子WorldのID（登録順）
+    children: Vec<String>,
+}
+
+/// 監視ループが受け取る通知
+enum Event {
+    /// 子Worldの実行が終了した（`generation` はその実行を開始した時点の世代）
+    Exited { index: usize, generation: u64 },
+    /// スーパーバイザー自身が停止された
+    Cancelled,
+}
+
+impl Supervisor {
+    /// `multiverse` に登録されたWorldを `strategy` で監視するスーパーバイザーを作成します。
+    ///
+    /// 再起動の許容回数は、既定で5秒間に3回です（`intensity` で変更できます）。
+    pub fn new(multiverse: Arc<Multiverse>, strategy: RestartStrategy) -> Self {
+        Supervisor {
+            multiverse: Arc::downgrade(&multiverse),
+            strategy,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            period: DEFAULT_PERIOD,
+            children: Vec::new(),
+        }
+    }
+
+    /// `period` の間に最大 `max_restarts` 回まで再起動を許容するよう設定します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, RestartStrategy, Supervisor, World, WorldError, WorldStatus};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let mv = Arc::new(Multiverse::new());
+    /// mv.add("flaky".to_string(), World::repeatable(|| -> i32 { panic!("always crashes") }))
+    ///     .unwrap();
+    ///
+    /// let supervisor = Supervisor::new(Arc::clone(&mv), RestartStrategy::OneForOne)
+    ///     .intensity(2, Duration::from_secs(10))
+    ///     .child("flaky")
+    ///     .into_world();
+    ///
+    /// // 2回再起動しても失敗し続けるため、3回目の失敗で監視を断念する
+    /// let Err(WorldError::Failed(failure)) = supervisor.run() else { panic!() };
+    /// assert_eq!(
+    ///     failure.downcast_error::<WorldError>(),
+    ///     Some(&WorldError::RestartLimitExceeded("flaky".to_string()))
+    /// );
+    /// assert_eq!(mv.history("flaky").unwrap().len(), 2);
+    /// assert!(matches!(mv.progress("flaky").unwrap(), WorldStatus::Failed(_)));
+    /// ```
+    pub fn intensity(self, max_restarts: u32, period: Duration) -> Self {
+        Supervisor {
+            max_restarts,
+            period,
+            ..self
+        }
+    }
+
+    /// 監視する子WorldのIDを追加します。
+    ///
+    /// 追加した順序は `RestartStrategy::RestForOne` で再起動する範囲の決定に使われます。
+    pub fn child(mut self, id: impl Into<String>) -> Self {
+        self.children.push(id.into());
+        self
+    }
+
+    /// 監視を行うWorldに変換します。
+    ///
+    /// このWorldを開始すると、まだ実行されていない子Worldを開始し（終了済みの子Worldは再起動し）、監視を始めます。
+    /// `stop()` されるか監視を断念すると、すべての子Worldを停止して終了します。
+    /// 返されるWorldは `World::repeatable` と同様に `reset()` で再実行できるため、
+    /// 別の `Supervisor` の子として `Multiverse` に登録できます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, RestartStrategy, Supervisor, World, WorldError};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let mv = Arc::new(Multiverse::new());
+    /// mv.add("flaky".to_string(), World::repeatable(|| -> i32 { panic!("always crashes") }))
+    ///     .unwrap();
+    ///
+    /// // 再起動を許容しない子のスーパーバイザーは、最初の失敗で親にエスカレーションする
+    /// let child = Supervisor::new(Arc::clone(&mv), RestartStrategy::OneForOne)
+    ///     .intensity(0, Duration::from_secs(10))
+    ///     .child("flaky");
+    /// mv.add("child_supervisor".to_string(), child.into_world()).unwrap();
+    ///
+    /// let parent = Supervisor::new(Arc::clone(&mv), RestartStrategy::OneForOne)
+    ///     .intensity(1, Duration::from_secs(10))
+    ///     .child("child_supervisor")
+    ///     .into_world();
+    ///
+    /// // 親は子のスーパーバイザーを1回再起動した後、監視を断念する
+    /// let Err(WorldError::Failed(failure)) = parent.run() else { panic!() };
+    /// assert_eq!(
+    ///     failure.downcast_error::<WorldError>(),
+    ///     Some(&WorldError::RestartLimitExceeded("child_supervisor".to_string()))
+    /// );
+    /// assert_eq!(mv.history("child_supervisor").unwrap().len(), 1);
+    ///
+    /// // スーパーバイザーは Multiverse を所有しないため、登録しても循環参照にならない
+    /// assert_eq!(Arc::strong_count(&mv), 1);
+    /// ```
+    pub fn into_world(self) -> World<()> {
+        let supervisor = Arc::new(self);
+        World::with_factory(Box::new(move || {
+            let supervisor = Arc::clone(&supervisor);
+            Box::new(move |ctx| supervisor.supervise(&ctx.cancel_token))
+        }))
+    }
+
+    /// 子Worldを開始し、停止されるか監視を断念するまで監視を続けます。
+    fn supervise(&self, cancel_token: &CancelToken) -> Result<(), Failure> {
+        let (tx, rx) = mpsc::channel();
+        let cancel_tx = tx.clone();
+        cancel_token.on_cancel(move || {
+            let _ = cancel_tx.send(Event::Cancelled);
+        });
+
+        let result = self.watch(&tx, &rx);
+        // 監視を終える際は、子Worldもすべて停止する
+        self.stop_children(0..self.children.len());
+        result.map_err(Failure::from_error)
+    }
+
+    /// 監視ループ本体です。停止された場合は `Ok` を、監視を断念した場合は `Err` を返します。
+    fn watch(
+        &self,
+        tx: &mpsc::Sender<Event>,
+        rx: &mpsc::Receiver<Event>,
+    ) -> Result<(), WorldError> {
+        // 子Worldの実行ごとに世代を進め、停止させた前回の実行からの通知を無視できるようにする
+        let mut generations = vec![0; self.children.len()];
+        let mut restarts = VecDeque::new();
+
+        for index in 0..self.children.len() {
+            self.launch(index, &mut generations, tx)?;
+        }
+
+        loop {
+            let (index, generation) = match rx.recv() {
+                Ok(Event::Exited { index, generation }) => (index, generation),
+                Ok(Event::Cancelled) | Err(_) => return Ok(()),
+            };
+            if generation != generations[index] {
+                continue;
+            }
+            let status = self.child_world(index)?.any_progress();
+            if !matches!(status, WorldStatus::Failed(_) | WorldStatus::TimedOut) {
+                continue; // 正常終了や外部からの停止では再起動しない
+            }
+
+            let now = Instant::now();
+            restarts.retain(|restarted_at| now.duration_since(*restarted_at) < self.period);
+            if restarts.len() >= self.max_restarts as usize {
+                return Err(WorldError::RestartLimitExceeded(
+                    self.children[index].clone(),
+                ));
+            }
+            restarts.push_back(now);
+
+            let targets = match self.strategy {
+                RestartStrategy::OneForOne => index..index + 1,
+                RestartStrategy::OneForAll => 0..self.children.len(),
+                RestartStrategy::RestForOne => index..self.children.len(),
+            };
+            self.stop_children(targets.clone());
+            for target in targets {
+                self.launch(target, &mut generations, tx)?;
+            }
+        }
+    }
+
+    /// 子Worldを開始（終了済みであれば再起動）し、終了時に通知されるようにします。
+    fn launch(
+        &self,
+        index: usize,
+        generations: &mut [u64],
+        tx: &mpsc::Sender<Event>,
+    ) -> Result<(), WorldError> {
+        let world = self.child_world(index)?;
+        if !world.any_progress().is_active() {
+            // 依存先の待機や前回の結果の破棄も含めて、Multiverseを通して再起動する
+            self.multiverse(index)?.restart(&self.children[index])?;
+        }
+
+        generations[index] += 1;
+        let generation = generations[index];
+        let tx = tx.clone();
+        world.any_on_complete(Box::new(move || {
+            let _ = tx.send(Event::Exited { index, generation });
+        }));
+        Ok(())
+    }
+
+    /// 指定された範囲の子Worldのうち、実行中のものを停止します。
+    fn stop_children(&self, range: Range<usize>) {
+        let Some(multiverse) = self.multiverse.upgrade() else {
+            return;
+        };
+        for id in &self.children[range] {
+            if let Some(world) = multiverse.get(id)
+                && world.any_progress().is_active()
+            {
+                let _ = world.any_stop(); // エラーは無視
+            }
+        }
+    }
+
+    /// 子Worldを `Multiverse` から取得します。
+    fn child_world(&self, index: usize) -> Result<Arc<dyn AnyWorld>, WorldError> {
+        let id = &self.children[index];
+        self.multiverse(index)?
+            .get(id)
+            .ok_or_else(|| WorldError::NotFound(id.clone()))
+    }
+
+    /// `index` 番目の子Worldが登録されている `Multiverse` を取得します。
+    ///
+    /// `Multiverse` が既に破棄されていれば、子Worldが見つからないものとして扱います。
+    fn multiverse(&self, index: usize) -> Result<Arc<Multiverse>, WorldError> {
+        self.multiverse
+            .upgrade()
+            .ok_or_else(|| WorldError::NotFound(self.children[index].clone()))
+    }
+}
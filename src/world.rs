@@ -1,25 +1,57 @@
-use std::any::Any;
+use crate::cancel::{self, CancelToken, Signal};
+use crate::error::WorldError;
+use crate::executor::{self, Executor, Priority};
+use crate::failure::{self, Failure};
+use crate::logs::{self, LogBuffer, LogLine, Logger, SharedLogBuffer};
+use crate::mailbox::InboxSlot;
+use crate::metrics::{self, Timings, WorldMetrics};
+use crate::process::{self, ChildSlot};
+use crate::report::{Report, Reporter};
+use crate::retry::RetryPolicy;
+use crate::stream::StreamSlot;
+use std::any::{Any, TypeId};
+use std::collections::VecDeque;
 use std::fmt;
-use std::panic::AssertUnwindSafe;
-use std::sync::{Arc, Mutex, mpsc};
+use std::future::{self, Future};
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, mpsc};
+use std::task::Poll;
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 /// Worldの実行状態を表す列挙型
 #[derive(Debug, Clone, PartialEq)]
 pub enum WorldStatus {
     /// タスクは作成されたばかりで、実行準備ができています。
     Ready,
+    /// タスクは依存するWorldの完了を待っています（`Multiverse::add_with_deps`）。
+    Blocked,
+    /// タスクは実行を開始しましたが、実行器（`Executor`）の空きを待っています。
+    Queued,
     /// タスクは現在実行中です。
     Running,
+    /// タスクは失敗し、再試行を待っています（`World::with_retry`）。
+    Retrying {
+        /// 次に行う試行が何回目か（初回を1とする）
+        attempt: u32,
+        /// 次の試行を開始する予定時刻
+        next_at: Instant,
+    },
     /// タスクは正常に実行を完了しました。
     Finished,
     /// タスクの実行中にエラーが発生し、失敗しました。
-    /// 失敗の具体的な理由を含む文字列を保持します。
-    Failed(String),
+    /// 失敗の理由（パニックのメッセージ、発生位置、バックトレース）を保持します。
+    Failed(Failure),
     /// タスクは外部からの指示により停止されました。
     Stopped,
+    /// 依存するWorldが失敗したため、タスクは実行されませんでした（`DependencyPolicy::Skip`）。
+    Skipped,
+    /// タスクは設定された制限時間（`World::with_timeout` / `Multiverse::add_with_deadline`）内に
+    /// 結果を返さなかったため、打ち切られました。
+    TimedOut,
     /// タスクは外部からの強力な指示により強制終了されました。
-    /// （現在の`std::thread`の制限により、`Stopped`とほぼ同じ挙動をします。）
+    /// `World::in_process` で作成されたプロセスベースのWorldを `kill()` した場合にのみ設定されます。
     Killed,
 }
 
@@ -31,20 +63,87 @@ impl fmt::Display for WorldStatus {
     /// use parallel_world::WorldStatus;
     ///
     /// assert_eq!(WorldStatus::Running.to_string(), "Running");
-    /// assert_eq!(WorldStatus::Failed("テストエラー".to_string()).to_string(), "Failed: テストエラー");
+    /// assert_eq!(WorldStatus::Failed("テストエラー".into()).to_string(), "Failed: テストエラー");
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             WorldStatus::Ready => write!(f, "Ready"),
+            WorldStatus::Blocked => write!(f, "Blocked"),
+            WorldStatus::Queued => write!(f, "Queued"),
             WorldStatus::Running => write!(f, "Running"),
+            WorldStatus::Retrying { attempt, .. } => write!(f, "Retrying (attempt {})", attempt),
             WorldStatus::Finished => write!(f, "Finished"),
             WorldStatus::Failed(e) => write!(f, "Failed: {}", e),
             WorldStatus::Stopped => write!(f, "Stopped"),
+            WorldStatus::Skipped => write!(f, "Skipped"),
+            WorldStatus::TimedOut => write!(f, "TimedOut"),
             WorldStatus::Killed => write!(f, "Killed"),
         }
     }
 }
 
+impl WorldStatus {
+    /// 状態の種類を表す名前を返します（`Failed` のメッセージや再試行の回数などは含みません）。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::WorldStatus;
+    ///
+    /// assert_eq!(WorldStatus::Failed("boom".into()).name(), "Failed");
+    /// assert_eq!(WorldStatus::Finished.name(), "Finished");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            WorldStatus::Ready => "Ready",
+            WorldStatus::Blocked => "Blocked",
+            WorldStatus::Queued => "Queued",
+            WorldStatus::Running => "Running",
+            WorldStatus::Retrying { .. } => "Retrying",
+            WorldStatus::Finished => "Finished",
+            WorldStatus::Failed(_) => "Failed",
+            WorldStatus::Stopped => "Stopped",
+            WorldStatus::Skipped => "Skipped",
+            WorldStatus::TimedOut => "TimedOut",
+            WorldStatus::Killed => "Killed",
+        }
+    }
+
+    /// タスクが実行中（`Running`）、再試行待ち（`Retrying`）、依存待ち（`Blocked`）、
+    /// または実行待ち（`Queued`）であれば`true`を返します。
+    ///
+    /// いずれの状態でも、Worldは停止でき、削除や再実行はできません。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Backoff, RetryPolicy, World, WorldStatus};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::new(3).backoff(Backoff::Fixed(Duration::from_secs(10)));
+    /// let world = World::with_retry(policy, || Err::<(), _>("always fails"));
+    /// world.start().unwrap();
+    /// sleep(Duration::from_millis(50)); // 1回目の失敗を待つ
+    ///
+    /// let status = world.progress();
+    /// assert!(matches!(status, WorldStatus::Retrying { attempt: 2, .. }));
+    /// assert!(status.is_active());
+    ///
+    /// // 再試行の待機中に停止すると、それ以上再試行しない
+    /// world.stop().unwrap();
+    /// assert!(world.status().is_err());
+    /// assert_eq!(world.progress(), WorldStatus::Stopped);
+    /// ```
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            WorldStatus::Running
+                | WorldStatus::Retrying { .. }
+                | WorldStatus::Blocked
+                | WorldStatus::Queued
+        )
+    }
+}
+
 /// # World
 ///
 /// `World` は `Multiverse` クレートで使用されるタスクの基本的な単位です。
@@ -56,20 +155,412 @@ impl fmt::Display for WorldStatus {
 pub struct World<R: Send + 'static> {
     /// 実行する関数（Job）
     process: WorldProcess<R>,
-    /// Worldの現在の状態
-    status: Arc<Mutex<WorldStatus>>,
+    /// 実行する関数を作り直すためのファクトリ（`repeatable` で作成された場合のみ）
+    factory: Option<JobFactory<R>>,
+    /// 現在の実行に関する状態。`reset()` のたびに作り直されます。
+    run: Mutex<RunState>,
+    /// 状態が変化したときに通知するリスナー（`Multiverse` に追加されたときに設定されます）
+    listener: StatusListenerSlot,
+    /// Worldが作成された時刻
+    created_at: Instant,
+    /// Worldが出力したログ（`reset()` しても破棄されません）
+    logs: SharedLogBuffer,
+    /// Worldの実行と状態の変化を記録するスパン（`Multiverse` に追加されるとIDが記録されます）
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    /// これまでの実行の結果（`reset()` された時点の状態）
+    history: Mutex<Vec<WorldStatus>>,
+    /// 結果を受け取る上流のWorld（`after` / `after2` で作成された場合のみ）
+    upstream: Option<Upstream>,
     /// 実行中のスレッドハンドル（Noneは未実行または実行完了/停止）
     thread_handle: WorldThreadHandle,
     /// タスクの実行結果を送信するためのチャネルの送信側。
+    /// 実行器で実行される場合は、実際に実行が始まるまでここに残ります。
     result_sender: WorldResultSender<R>,
     /// タスクの実行結果を受信するためのチャネルの受信側。
     result_receiver: WorldResultReceiver<R>,
+    /// プロセスベースのWorldが実行中の子プロセス（スレッドで実行されるWorldでは`None`）
+    pub(crate) child: Option<ChildSlot>,
+    /// 送り出されるアイテムを受け取る `Stream`（`with_emitter` で作成された場合のみ）
+    pub(crate) stream: Option<StreamSlot>,
+    /// メッセージを受け取る受信箱の送信側（`with_inbox` で作成された場合のみ）
+    pub(crate) inbox: Option<Arc<InboxSlot>>,
+    /// `kill()` でSIGTERMを送ってからSIGKILLを送るまでの猶予期間
+    kill_grace: Duration,
+    /// 実行開始からの制限時間
+    timeout: Option<Duration>,
+    /// 実行に使う実行器（`None` の場合は、実行のたびにスレッドを作成します）
+    executor: Option<Arc<dyn Executor>>,
+    /// 実行器で実行を待つ間の優先度
+    priority: Priority,
+    /// 実行の開始・終了時に呼び出されるフック
+    hooks: Hooks<R>,
+    /// 結果を返さなければならない期限（`with_deadline` で設定された絶対時刻）
+    deadline: Option<Instant>,
+    /// 現在の実行の期限。`Ready` から抜けるときに `timeout` と `deadline` から確定します。
+    run_deadline: Mutex<Option<Instant>>,
+}
+
+/// 1回の実行の間、実行スレッドや監視スレッドと共有される状態
+///
+/// `reset()` で新しいものに置き換えられるため、前回の実行のスレッドが残っていても、
+/// 新しい実行の状態が書き換えられることはありません。
+#[derive(Clone)]
+struct RunState {
+    /// Worldの現在の状態
+    status: Arc<StatusCell>,
+    /// 協調的な停止のためのキャンセルトークン。`stop()` でキャンセルされます。
+    cancel_token: CancelToken,
+    /// 結果が受け取れる状態になった（または期限切れになった）ときに発火するシグナル
+    completion: Signal,
+    /// パニックした場合の元のペイロード（`take_panic_payload()` で取り出せます）
+    panic_payload: PanicPayload,
+    /// 実行中に報告された進捗（`report()` で取得できます）
+    report: Arc<Mutex<Report>>,
+    /// 実行の開始・終了の時刻とCPU時間（`metrics()` で取得できます）
+    timings: Arc<Mutex<Timings>>,
+    /// 結果が送られる前に結果を参照する関数（`on_result()` で登録されます）
+    result_taps: ResultTaps,
+}
+
+/// パニックした場合の元のペイロードの置き場所
+type PanicPayload = Arc<Mutex<Option<Box<dyn Any + Send>>>>;
+
+/// `Finished` になったWorldの結果を、呼び出し側に送られる前に参照する関数
+pub(crate) type ResultTap = Box<dyn FnOnce(&(dyn Any + Send)) + Send + 'static>;
+/// 結果が送られるまでに登録された `ResultTap`。結果が送られた後は `None` になります。
+type ResultTaps = Arc<Mutex<Option<Vec<ResultTap>>>>;
+
+/// 状態が変化したときに、変化前と変化後の状態を受け取る関数
+pub(crate) type StatusListener = Arc<dyn Fn(&WorldStatus, &WorldStatus) + Send + Sync>;
+/// 実行をまたいで共有される `StatusListener` の置き場所
+type StatusListenerSlot = Arc<Mutex<Option<StatusListener>>>;
+
+/// Worldの状態と、状態が変化したときに通知するリスナー
+pub(crate) struct StatusCell {
+    status: Mutex<WorldStatus>,
+    listener: StatusListenerSlot,
+    /// リスナーにまだ通知していない状態の変化（変化前, 変化後）。発生した順に並びます。
+    pending: Mutex<VecDeque<(WorldStatus, WorldStatus)>>,
+    /// 通知の順序を保つためのロック。保持しているスレッドだけが `pending` から取り出して通知します。
+    notifying: Mutex<()>,
+}
+
+impl StatusCell {
+    fn new(listener: &StatusListenerSlot) -> Self {
+        StatusCell {
+            status: Mutex::new(WorldStatus::Ready),
+            listener: Arc::clone(listener),
+            pending: Mutex::new(VecDeque::new()),
+            notifying: Mutex::new(()),
+        }
+    }
+
+    /// 状態をロックします。ロック中に状態を書き換えると、ロックを解放した後でリスナーに通知されます。
+    pub(crate) fn lock(&self) -> StatusGuard<'_> {
+        StatusGuard {
+            guard: Some(self.status.lock().unwrap()),
+            from: None,
+            cell: self,
+        }
+    }
+
+    /// 溜まっている状態の変化を、発生した順にリスナーに通知します。
+    ///
+    /// 別のスレッドが通知中であれば、その通知が終わるのを待ってから残りを通知します。
+    /// そのため、状態を書き換えたスレッドに処理が戻る時点で、その変化は通知済みです。
+    fn notify_pending(&self) {
+        let _notifying = self.notifying.lock().unwrap();
+        loop {
+            let next = self.pending.lock().unwrap().pop_front();
+            let Some((from, to)) = next else { return };
+            let listener = self.listener.lock().unwrap().clone();
+            if let Some(listener) = listener {
+                listener(&from, &to);
+            }
+        }
+    }
+}
+
+/// 状態の変化を `span` のイベントとして記録し、`then` があれば続けて呼び出すリスナーを作成します。
+#[cfg(feature = "tracing")]
+fn traced_listener(span: &tracing::Span, then: Option<StatusListener>) -> StatusListener {
+    let span = span.clone();
+    Arc::new(move |from, to| {
+        tracing::info!(parent: &span, %from, %to, "world status changed");
+        if let Some(then) = &then {
+            then(from, to);
+        }
+    })
+}
+
+/// `StatusCell` のロックガード
+///
+/// 書き換えのために参照されたときの状態を記録しておき、破棄されるときに状態が変わっていればリスナーに通知します。
+/// 変化はロックを保持したまま `pending` に追加し、ロックを解放してから通知するため、
+/// リスナーの中から `progress()` などで同じWorldの状態を参照できます。同じWorldの状態の変化は発生した順に通知されます。
+pub(crate) struct StatusGuard<'a> {
+    /// 状態のロック。破棄するときに、リスナーに通知する前に解放します。
+    guard: Option<MutexGuard<'a, WorldStatus>>,
+    from: Option<WorldStatus>,
+    cell: &'a StatusCell,
+}
+
+impl Deref for StatusGuard<'_> {
+    type Target = WorldStatus;
+
+    fn deref(&self) -> &WorldStatus {
+        self.guard.as_ref().expect("status lock is held until drop")
+    }
+}
+
+impl DerefMut for StatusGuard<'_> {
+    fn deref_mut(&mut self) -> &mut WorldStatus {
+        let guard = self.guard.as_mut().expect("status lock is held until drop");
+        if self.from.is_none() {
+            self.from = Some(guard.clone());
+        }
+        guard
+    }
+}
+
+impl Drop for StatusGuard<'_> {
+    fn drop(&mut self) {
+        let Some(guard) = self.guard.take() else {
+            return;
+        };
+        let Some(from) = self.from.take() else {
+            return;
+        };
+        if from == *guard {
+            return;
+        }
+        let to = guard.clone();
+        // ロックを保持したまま追加するため、`pending` には状態が変化した順に並ぶ
+        self.cell.pending.lock().unwrap().push_back((from, to));
+        drop(guard);
+        self.cell.notify_pending();
+    }
+}
+
+impl RunState {
+    fn new(listener: &StatusListenerSlot) -> Self {
+        RunState {
+            status: Arc::new(StatusCell::new(listener)),
+            cancel_token: CancelToken::new(),
+            completion: Signal::new(),
+            panic_payload: Arc::new(Mutex::new(None)),
+            report: Arc::new(Mutex::new(Report::default())),
+            timings: Arc::new(Mutex::new(Timings::default())),
+            result_taps: Arc::new(Mutex::new(Some(Vec::new()))),
+        }
+    }
+}
+
+type WorldProcess<R> = Mutex<Option<Job<R>>>;
+/// Worldが実行する関数。`from` で作成された場合は常に`Ok`を返します。
+pub(crate) type WorldJob<R> = Box<dyn FnOnce(&JobContext) -> Result<R, Failure> + Send + 'static>;
+/// Worldが実行するFuture（`from_future` で作成された場合）
+type WorldFuture<R> = Pin<Box<dyn Future<Output = R> + Send + 'static>>;
+
+/// Worldが実行する処理
+enum Job<R> {
+    /// スレッドを占有して実行する関数
+    Blocking(WorldJob<R>),
+    /// 実行器の上でポーリングされるFuture
+    Future(WorldFuture<R>),
+}
+
+/// 実行スレッド（またはFuture）から、実行の開始と終了をWorldの状態に反映するためのハンドル
+struct JobRunner<R> {
+    status: Arc<StatusCell>,
+    panic_payload: PanicPayload,
+    sender_slot: WorldResultSender<R>,
+    completion: Signal,
+    hooks: Hooks<R>,
+    timings: Arc<Mutex<Timings>>,
+    result_taps: ResultTaps,
+}
+
+impl<R: Send + 'static> JobRunner<R> {
+    /// 実行器のキューから取り出されたときに呼び出し、`Queued` から `Running` に遷移させて結果の送信側を受け取ります。
+    ///
+    /// キューで待っている間に停止・期限切れになっていれば、`None` を返します（実行してはいけません）。
+    fn begin(&self) -> Option<mpsc::Sender<Result<R, WorldError>>> {
+        let mut s = self.status.lock();
+        if *s != WorldStatus::Queued {
+            return None;
+        }
+        *s = WorldStatus::Running;
+        drop(s);
+        self.timings.lock().unwrap().started_at = Some(Instant::now());
+        self.sender_slot.lock().unwrap().take()
+    }
+
+    /// `f` を実行し、パニックした場合は元のペイロードを保持して `Failure` を返します。
+    /// 実行したスレッドが消費したCPU時間も記録します。Futureはポーリングごとに別のスレッドで
+    /// 実行されることがあるため、呼び出しごとに計測して合算します。
+    fn catch_panic<T>(&self, f: impl FnOnce() -> Result<T, Failure>) -> Result<T, Failure> {
+        let cpu_before = metrics::thread_cpu_time();
+        let result = failure::catch_panic(f);
+        self.timings.lock().unwrap().add_cpu_time_since(cpu_before);
+        match result {
+            Ok(outcome) => outcome, // 正常終了、またはクロージャが返したエラー
+            Err((failure, payload)) => {
+                // 呼び出し側で resume_unwind できるよう、元のペイロードを保持しておく
+                *self.panic_payload.lock().unwrap() = Some(payload);
+                Err(failure)
+            }
+        }
+    }
+
+    /// 実行結果を状態に反映して送信し、完了を通知します。
+    ///
+    /// `outcome` が `None` の場合は、停止などによって実行が打ち切られたことを表します。
+    fn finish(
+        &self,
+        outcome: Option<Result<R, Failure>>,
+        result_sender: mpsc::Sender<Result<R, WorldError>>,
+    ) {
+        let mut s = self.status.lock();
+        let mut failed = None;
+        let result = match (outcome, &*s) {
+            // 強制終了されたWorldの結果は破棄する
+            (_, WorldStatus::Killed) => Err(WorldError::Killed),
+            // 期限切れのWorldの結果も破棄する
+            (_, WorldStatus::TimedOut) => Err(WorldError::TimedOut),
+            (None, _) => Err(WorldError::Stopped),
+            (Some(Ok(val)), WorldStatus::Stopped) => Ok(val),
+            // 停止後に失敗した場合も、状態はStoppedのままにする
+            (Some(Err(failure)), WorldStatus::Stopped) => Err(failure_error(&failure)),
+            // Stopped/Killed/TimedOutが設定されていなければFinished
+            (Some(Ok(val)), _) => {
+                *s = WorldStatus::Finished;
+                Ok(val)
+            }
+            (Some(Err(failure)), _) => {
+                let error = failure_error(&failure);
+                failed = Some(failure.clone());
+                *s = WorldStatus::Failed(failure);
+                Err(error)
+            }
+        };
+        let finished = *s == WorldStatus::Finished;
+        drop(s);
+        self.timings.lock().unwrap().finished_at = Some(Instant::now());
+
+        // フックは結果を送る前に呼び出す（status()が返った時点で後始末が終わっているように）
+        match (&result, failed) {
+            (Ok(val), _) if finished => self.hooks.call(&self.hooks.on_finish, |f| f(val)),
+            (_, Some(failure)) => self.hooks.call(&self.hooks.on_fail, |f| f(&failure)),
+            _ => self.hooks.call(&self.hooks.on_stop, |f| f()),
+        }
+        // 下流のWorldへの受け渡しも結果を送る前に済ませる（呼び出し側が結果を取り出すのと競合しないように）
+        let taps = self.result_taps.lock().unwrap().take().unwrap_or_default();
+        if finished && let Ok(val) = &result {
+            for tap in taps {
+                tap(val);
+            }
+        }
+        let _ = result_sender.send(result);
+        self.completion.fire(); // 結果を受け取れるようになったことを通知する（監視スレッドも終了する）
+    }
+}
+
+/// 引数を取らないフック（`on_start` / `on_stop`）
+type Hook = Arc<dyn Fn() + Send + Sync>;
+/// 結果を受け取るフック（`on_finish`）
+type FinishHook<R> = Arc<dyn Fn(&R) + Send + Sync>;
+/// 失敗の詳細を受け取るフック（`on_fail`）
+type FailHook = Arc<dyn Fn(&Failure) + Send + Sync>;
+
+/// 実行の開始・終了時に、実行スレッドで呼び出されるフック
+struct Hooks<R> {
+    on_start: Option<Hook>,
+    on_finish: Option<FinishHook<R>>,
+    on_fail: Option<FailHook>,
+    on_stop: Option<Hook>,
+}
+
+impl<R> Hooks<R> {
+    /// フックが設定されていれば呼び出します。フックがパニックしても、Worldの結果には影響させません。
+    fn call<F: ?Sized>(&self, hook: &Option<Arc<F>>, call: impl FnOnce(&F)) {
+        if let Some(hook) = hook {
+            let _ = failure::catch_panic(|| call(hook));
+        }
+    }
+}
+
+impl<R> Default for Hooks<R> {
+    fn default() -> Self {
+        Hooks {
+            on_start: None,
+            on_finish: None,
+            on_fail: None,
+            on_stop: None,
+        }
+    }
+}
+
+impl<R> Clone for Hooks<R> {
+    fn clone(&self) -> Self {
+        Hooks {
+            on_start: self.on_start.clone(),
+            on_finish: self.on_finish.clone(),
+            on_fail: self.on_fail.clone(),
+            on_stop: self.on_stop.clone(),
+        }
+    }
+}
+
+/// 上流のWorldの結果を受け取るための情報
+struct Upstream {
+    /// 上流のWorldのID（引数の順）
+    ids: Vec<String>,
+    /// 上流の結果を引数の型にダウンキャストして複製する関数（引数の順）
+    cloners: Vec<InputCloner>,
+    /// 受け取った上流の結果（引数の順）。型が一致しなかった場合は、そのエラーを保持します。
+    inputs: Inputs,
+}
+
+/// 上流の結果（値と、その型の名前）を受け取り、Jobの引数として複製する関数
+type InputCloner = fn(&(dyn Any + Send), &'static str) -> Result<Box<dyn Any + Send>, WorldError>;
+/// Jobの引数として受け取った上流の結果（まだ受け取っていなければ `None`）
+type InputSlot = Option<Result<Box<dyn Any + Send>, WorldError>>;
+/// 上流のWorldごとの `InputSlot`
+type Inputs = Arc<Mutex<Vec<InputSlot>>>;
+
+/// 実行のたびに新しい `WorldJob` を作成する関数
+pub(crate) type JobFactory<R> = Box<dyn Fn() -> WorldJob<R> + Send + Sync + 'static>;
+
+/// 実行中の関数（Job）からWorldの状態にアクセスするためのコンテキスト
+pub(crate) struct JobContext {
+    /// Worldの現在の状態
+    pub(crate) status: Arc<StatusCell>,
+    /// Worldのキャンセルトークン
+    pub(crate) cancel_token: CancelToken,
+    /// 実行中に報告された進捗
+    pub(crate) report: Arc<Mutex<Report>>,
+    /// Worldが出力したログ
+    pub(crate) logs: SharedLogBuffer,
+}
+
+impl JobContext {
+    /// Worldが実行中（または再試行待ち）であれば状態を `status` に更新し、`true` を返します。
+    /// 既に停止・期限切れなどになっている場合は何もせず `false` を返します。
+    pub(crate) fn set_status_if_active(&self, status: WorldStatus) -> bool {
+        let mut guard = self.status.lock();
+        if !guard.is_active() {
+            return false;
+        }
+        *guard = status;
+        true
+    }
 }
 
-type WorldProcess<R> = Mutex<Option<Box<dyn FnOnce() -> R + Send + 'static>>>;
 type WorldThreadHandle = Mutex<Option<JoinHandle<()>>>;
-type WorldResultSender<R> = Mutex<Option<mpsc::Sender<Result<R, String>>>>;
-type WorldResultReceiver<R> = Arc<Mutex<Option<mpsc::Receiver<Result<R, String>>>>>;
+type WorldResultSender<R> = Arc<Mutex<Option<mpsc::Sender<Result<R, WorldError>>>>>;
+type WorldResultReceiver<R> = Arc<Mutex<Option<mpsc::Receiver<Result<R, WorldError>>>>>;
 
 impl<R: Send + 'static> Default for World<R> {
     fn default() -> Self {
@@ -90,16 +581,60 @@ impl<R: Send + 'static> World<R> {
     /// assert_eq!(world.progress(), WorldStatus::Ready);
     /// ```
     pub fn new() -> Self {
+        Self::with_job(None)
+    }
+
+    /// 実行する関数（Job）を指定してWorldを作成します。各コンストラクタの共通処理です。
+    pub(crate) fn with_job(job: Option<WorldJob<R>>) -> Self {
         let (tx, rx) = mpsc::channel();
+        let listener = Arc::new(Mutex::new(None));
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("world", id = tracing::field::Empty);
+        #[cfg(feature = "tracing")]
+        {
+            *listener.lock().unwrap() = Some(traced_listener(&span, None));
+        }
         World {
-            process: Mutex::new(None),
-            status: Arc::new(Mutex::new(WorldStatus::Ready)),
+            process: Mutex::new(job.map(Job::Blocking)),
+            factory: None,
+            run: Mutex::new(RunState::new(&listener)),
+            listener,
+            created_at: Instant::now(),
+            logs: Arc::new(Mutex::new(LogBuffer::new(logs::DEFAULT_LOG_CAPACITY))),
+            #[cfg(feature = "tracing")]
+            span,
+            history: Mutex::new(Vec::new()),
+            upstream: None,
             thread_handle: Mutex::new(None),
-            result_sender: Mutex::new(Some(tx)),
+            result_sender: Arc::new(Mutex::new(Some(tx))),
             result_receiver: Arc::new(Mutex::new(Some(rx))),
+            child: None,
+            stream: None,
+            inbox: None,
+            kill_grace: process::DEFAULT_KILL_GRACE,
+            timeout: None,
+            executor: None,
+            priority: Priority::default(),
+            hooks: Hooks::default(),
+            deadline: None,
+            run_deadline: Mutex::new(None),
+        }
+    }
+
+    /// 実行のたびにJobを作り直すファクトリを指定して、再実行可能なWorldを作成します。
+    pub(crate) fn with_factory(factory: JobFactory<R>) -> Self {
+        let job = factory();
+        World {
+            factory: Some(factory),
+            ..Self::with_job(Some(job))
         }
     }
 
+    /// 現在の実行に関する状態を取得します。
+    fn run_state(&self) -> RunState {
+        self.run.lock().unwrap().clone()
+    }
+
     /// 関数からWorldを作成します。
     ///
     /// # 型引数
@@ -128,22 +663,679 @@ impl<R: Send + 'static> World<R> {
     where
         F: FnOnce() -> R + Send + 'static,
     {
-        let (tx, rx) = mpsc::channel();
+        Self::with_job(Some(Box::new(move |_| Ok(f()))))
+    }
+
+    /// `Result` を返す関数からWorldを作成します。
+    ///
+    /// クロージャが `Ok(r)` を返した場合は `from` と同様に `WorldStatus::Finished` になり、`status()` は `Ok(r)` を返します。
+    /// `Err(e)` を返した場合はパニックしなくても `WorldStatus::Failed` になり、`status()` は
+    /// `Err(WorldError::Failed(failure))` を返します。元のエラー `e` は `Failure::downcast_error` で取り出せます。
+    ///
+    /// # 型引数
+    /// * `E` - クロージャが返すエラーの型。`Display + Send + Sync + 'static` を満たす必要があります。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldError, WorldStatus};
+    /// use std::num::ParseIntError;
+    ///
+    /// let ok = World::try_from(|| "42".parse::<i32>());
+    /// assert_eq!(ok.run(), Ok(42));
+    ///
+    /// let failing = World::try_from(|| "forty-two".parse::<i32>());
+    /// let Err(WorldError::Failed(failure)) = failing.run() else { panic!() };
+    /// assert!(failure.downcast_error::<ParseIntError>().is_some());
+    /// assert!(matches!(failing.progress(), WorldStatus::Failed(_)));
+    /// ```
+    pub fn try_from<F, E>(f: F) -> Self
+    where
+        F: FnOnce() -> Result<R, E> + Send + 'static,
+        E: fmt::Display + Send + Sync + 'static,
+    {
+        Self::with_job(Some(Box::new(move |_| f().map_err(Failure::from_error))))
+    }
+
+    /// キャンセルトークンを受け取る関数からWorldを作成します。
+    ///
+    /// クロージャには、このWorldに紐づいた `CancelToken` が渡されます。
+    /// `stop()`（および `Multiverse::kill` / `Multiverse::stop_all`）が呼ばれるとトークンがキャンセルされるため、
+    /// クロージャは `is_cancelled()` や `sleep_or_cancel()` で停止要求に応答できます。
+    ///
+    /// # 引数
+    /// * `f` - この`World`が実行するクロージャ。`CancelToken` を引数に取ります。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldStatus};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let world = World::with_cancel(|token| {
+    ///     let mut counter = 0;
+    ///     while !token.sleep_or_cancel(Duration::from_millis(10)) {
+    ///         counter += 1;
+    ///     }
+    ///     counter
+    /// });
+    ///
+    /// world.start().unwrap();
+    /// sleep(Duration::from_millis(50));
+    /// world.stop().unwrap(); // トークンがキャンセルされ、ループが終了する
+    /// assert!(world.status().is_ok());
+    /// assert_eq!(world.progress(), WorldStatus::Stopped);
+    /// ```
+    pub fn with_cancel<F>(f: F) -> Self
+    where
+        F: FnOnce(CancelToken) -> R + Send + 'static,
+    {
+        Self::with_job(Some(Box::new(move |ctx| Ok(f(ctx.cancel_token.clone())))))
+    }
+
+    /// 進捗を報告するハンドルを受け取る関数からWorldを作成します。
+    ///
+    /// クロージャには、このWorldに紐づいた `Reporter` が渡されます。
+    /// 報告された進捗（割合・処理済みの数・メッセージ）は、実行中でも `report()` や `Multiverse::reports` で取得できます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    ///
+    /// let world = World::with_reporter(|reporter| {
+    ///     let items = 1000;
+    ///     for i in 0..items {
+    ///         // ... 1件ずつ処理する ...
+    ///         reporter.set_step(i + 1, items);
+    ///     }
+    ///     reporter.set_message("done");
+    ///     items
+    /// });
+    ///
+    /// assert_eq!(world.run(), Ok(1000));
+    /// let report = world.report();
+    /// assert_eq!(report.fraction, Some(1.0));
+    /// assert_eq!(report.step, Some((1000, 1000)));
+    /// assert_eq!(report.message.as_deref(), Some("done"));
+    /// ```
+    pub fn with_reporter<F>(f: F) -> Self
+    where
+        F: FnOnce(Reporter) -> R + Send + 'static,
+    {
+        Self::with_job(Some(Box::new(move |ctx| {
+            Ok(f(Reporter::new(Arc::clone(&ctx.report))))
+        })))
+    }
+
+    /// ログを記録するハンドルを受け取る関数からWorldを作成します。
+    ///
+    /// クロージャには、このWorldに紐づいた `Logger` が渡されます。
+    /// 記録した行はWorldごとのリングバッファ（既定では最新の1000行、`with_log_capacity` で変更可能）に保持され、
+    /// Worldが失敗した後でも `logs()` や `Multiverse::logs` で取得できます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::io::Write;
+    ///
+    /// let world = World::with_logger(|mut logger| {
+    ///     logger.log("starting");
+    ///     writeln!(logger, "step {}", 1).unwrap();
+    ///     panic!("boom");
+    /// });
+    ///
+    /// assert!(world.run().is_err());
+    /// let lines: Vec<_> = world.logs().into_iter().map(|line| line.text).collect();
+    /// assert_eq!(lines, ["starting", "step 1"]);
+    /// ```
+    pub fn with_logger<F>(f: F) -> Self
+    where
+        F: FnOnce(Logger) -> R + Send + 'static,
+    {
+        Self::with_job(Some(Box::new(move |ctx| {
+            Ok(f(Logger::new(Arc::clone(&ctx.logs))))
+        })))
+    }
+
+    /// 再試行ポリシー付きでWorldを作成します。
+    ///
+    /// `f` は `Fn` であり、パニックするか `Err` を返して失敗すると、`policy` に従って再実行されます。
+    /// 再試行を待っている間、Worldの状態は `WorldStatus::Retrying { attempt, next_at }` になります。
+    /// 最大試行回数に達するか、`RetryPolicy::retry_if` の条件を満たさない失敗が起きると、
+    /// 最後の失敗で `WorldStatus::Failed` になります。
+    /// 待機中に `stop()` された場合は、それ以上再試行しません。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Backoff, RetryPolicy, World, WorldStatus};
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use std::time::Duration;
+    ///
+    /// let calls = Arc::new(AtomicU32::new(0));
+    /// let calls_clone = Arc::clone(&calls);
+    /// let policy = RetryPolicy::new(3).backoff(Backoff::Fixed(Duration::from_millis(5)));
+    /// let world = World::with_retry(policy, move || {
+    ///     // 3回目でようやく成功する
+    ///     match calls_clone.fetch_add(1, Ordering::SeqCst) {
+    ///         0 => panic!("first attempt panics"),
+    ///         1 => Err("second attempt fails"),
+    ///         n => Ok(n),
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(world.run(), Ok(2));
+    /// assert_eq!(calls.load(Ordering::SeqCst), 3);
+    /// assert_eq!(world.progress(), WorldStatus::Finished);
+    /// ```
+    pub fn with_retry<F, E>(policy: RetryPolicy, f: F) -> Self
+    where
+        F: Fn() -> Result<R, E> + Send + Sync + 'static,
+        E: fmt::Display + Send + Sync + 'static,
+    {
+        Self::with_job(Some(Box::new(move |ctx| {
+            let mut attempt = 1;
+            loop {
+                let (failure, payload) = match failure::catch_panic(&f) {
+                    Ok(Ok(val)) => return Ok(val),
+                    Ok(Err(e)) => (Failure::from_error(e), None),
+                    Err((failure, payload)) => (failure, Some(payload)),
+                };
+
+                let retried = match policy.next_delay(attempt, &failure) {
+                    Some(delay) if !ctx.cancel_token.is_cancelled() => {
+                        attempt += 1;
+                        let next_at = Instant::now() + delay;
+                        // 待機中に停止・期限切れになった場合は、それ以上再試行しない
+                        ctx.set_status_if_active(WorldStatus::Retrying { attempt, next_at })
+                            && !ctx.cancel_token.sleep_or_cancel(delay)
+                            && ctx.set_status_if_active(WorldStatus::Running)
+                    }
+                    _ => false,
+                };
+                if !retried {
+                    // 最後の失敗をそのままWorldの失敗とする
+                    return match payload {
+                        Some(payload) => failure::resume_panic(failure, payload),
+                        None => Err(failure),
+                    };
+                }
+            }
+        })))
+    }
+
+    /// 何度でも再実行できるWorldを作成します。
+    ///
+    /// `from` と同様ですが、`f` は `Fn` であり、完了・失敗・停止した後に `reset()` すると
+    /// 再び `Ready` に戻して同じ関数を実行し直せます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldStatus};
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    ///
+    /// let runs = Arc::new(AtomicU32::new(0));
+    /// let runs_clone = Arc::clone(&runs);
+    /// let world = World::repeatable(move || runs_clone.fetch_add(1, Ordering::SeqCst) + 1);
+    ///
+    /// assert_eq!(world.run(), Ok(1));
+    /// world.reset().unwrap();
+    /// assert_eq!(world.progress(), WorldStatus::Ready);
+    /// assert_eq!(world.run(), Ok(2));
+    /// ```
+    pub fn repeatable<F>(f: F) -> Self
+    where
+        F: Fn() -> R + Send + Sync + 'static,
+    {
+        let f = Arc::new(f);
+        Self::with_factory(Box::new(move || {
+            let f = Arc::clone(&f);
+            Box::new(move |_| Ok(f()))
+        }))
+    }
+
+    /// `Future` からWorldを作成します。
+    ///
+    /// Futureはスレッドを占有せず、実行器の上で起こされたときだけポーリングされます。
+    /// 実行器が設定されていなければ（`with_executor` / `Multiverse::with_executor` を参照）、
+    /// すべてのFutureを1つのスレッドで実行する組み込みの実行器が使われます。
+    /// そのため、Futureの中でスレッドをブロックする処理（`std::thread::sleep` など）は避けてください。
+    ///
+    /// 状態の遷移は他のWorldと同じです。`stop()` や期限切れになると、Futureは完了を待たずに破棄されます。
+    /// ポーリング中のパニックは `from` と同様に `WorldStatus::Failed` になります。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldError, WorldStatus};
+    /// use std::future;
+    /// use std::time::Duration;
+    ///
+    /// let world = World::from_future(async { 1 + 2 });
+    /// assert_eq!(world.run(), Ok(3));
+    ///
+    /// // 停止すると、完了しないFutureも破棄される
+    /// let world = World::from_future(future::pending::<i32>());
+    /// world.start().unwrap();
+    /// world.stop().unwrap();
+    /// assert_eq!(world.status(), Err(WorldError::Stopped));
+    /// assert_eq!(world.progress(), WorldStatus::Stopped);
+    ///
+    /// let world = World::from_future(future::pending::<()>()).with_timeout(Duration::from_millis(20));
+    /// assert_eq!(world.run(), Err(WorldError::TimedOut));
+    /// ```
+    pub fn from_future<F>(future: F) -> Self
+    where
+        F: Future<Output = R> + Send + 'static,
+    {
         World {
-            process: Mutex::new(Some(Box::new(f))),
-            status: Arc::new(Mutex::new(WorldStatus::Ready)),
-            thread_handle: Mutex::new(None),
-            result_sender: Mutex::new(Some(tx)),
-            result_receiver: Arc::new(Mutex::new(Some(rx))),
+            process: Mutex::new(Some(Job::Future(Box::pin(future)))),
+            ..Self::new()
         }
     }
 
+    /// 上流のWorldの結果を受け取って実行するWorldを作成します。
+    ///
+    /// `Multiverse` に追加すると、`a` のWorldに依存するWorldとして扱われ（`Multiverse::add_with_deps` を参照）、
+    /// `a` が `Finished` になると、その結果が複製されて `f` に渡されます。
+    /// 結果は上流のWorldが結果を返す前に複製されるため、上流の結果は引き続き `Multiverse::status` で取得できます。
+    ///
+    /// 上流の結果が期待する型 `A` と異なる場合や、
+    /// 上流の完了後に実行開始されたときに上流の結果が既に取得されていた場合は、このWorldは実行されずに失敗します。
+    /// `Multiverse` を介さずに開始した場合も、結果を受け取れないため失敗します（`WorldError::InputsNotProvided`）。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World};
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("fetch".to_string(), World::from(|| vec![3, 1, 2])).unwrap();
+    /// pw.add("sum".to_string(), World::after("fetch", |v: Vec<i32>| v.iter().sum::<i32>())).unwrap();
+    ///
+    /// pw.start_all();
+    /// assert_eq!(pw.status::<i32>("sum"), Ok(6));
+    /// assert_eq!(pw.status::<Vec<i32>>("fetch"), Ok(vec![3, 1, 2])); // 上流の結果も取得できる
+    ///
+    /// // 下流のWorldより先に上流の結果を取得しても、下流のWorldには結果が渡される
+    /// for _ in 0..20 {
+    ///     let pw = Multiverse::new();
+    ///     pw.add("fetch".to_string(), World::from(|| vec![3, 1, 2])).unwrap();
+    ///     pw.add("sum".to_string(), World::after("fetch", |v: Vec<i32>| v.iter().sum::<i32>())).unwrap();
+    ///
+    ///     pw.start_all();
+    ///     assert_eq!(pw.status::<Vec<i32>>("fetch"), Ok(vec![3, 1, 2]));
+    ///     assert_eq!(pw.status::<i32>("sum"), Ok(6));
+    /// }
+    /// ```
+    pub fn after<A, F>(a: &str, f: F) -> Self
+    where
+        A: Clone + Send + 'static,
+        F: FnOnce(A) -> R + Send + 'static,
+    {
+        let inputs = Arc::new(Mutex::new(vec![None]));
+        let inputs_clone = Arc::clone(&inputs);
+        let world = Self::with_job(Some(Box::new(move |_| {
+            let [a] = take_inputs(&inputs_clone)?;
+            Ok(f(into_input(a)))
+        })));
+        World {
+            upstream: Some(Upstream {
+                ids: vec![a.to_string()],
+                cloners: vec![clone_input::<A>],
+                inputs,
+            }),
+            ..world
+        }
+    }
+
+    /// 2つの上流のWorldの結果を受け取って実行するWorldを作成します。
+    ///
+    /// `a` と `b` の両方が `Finished` になると、それぞれの結果が複製されて `f` に渡されます。
+    /// 詳しくは `after` を参照してください。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldError};
+    ///
+    /// let pw = Multiverse::new();
+    /// pw.add("A".to_string(), World::from(|| 2)).unwrap();
+    /// pw.add("B".to_string(), World::from(|| "apples".to_string())).unwrap();
+    /// pw.add("C".to_string(), World::after2("A", "B", |a: i32, b: String| format!("{} {}", a, b)))
+    ///     .unwrap();
+    ///
+    /// pw.start_all();
+    /// assert_eq!(pw.status::<String>("C"), Ok("2 apples".to_string()));
+    ///
+    /// // 型が一致しない場合は実行されずに失敗する
+    /// pw.add("D".to_string(), World::after("A", |a: String| a)).unwrap();
+    /// pw.exec("D").unwrap();
+    /// let Err(WorldError::Failed(failure)) = pw.status::<String>("D") else { panic!() };
+    /// assert!(matches!(
+    ///     failure.downcast_error::<WorldError>(),
+    ///     Some(WorldError::DowncastFailed { actual: "i32", .. })
+    /// ));
+    /// ```
+    pub fn after2<A, B, F>(a: &str, b: &str, f: F) -> Self
+    where
+        A: Clone + Send + 'static,
+        B: Clone + Send + 'static,
+        F: FnOnce(A, B) -> R + Send + 'static,
+    {
+        let inputs = Arc::new(Mutex::new(vec![None, None]));
+        let inputs_clone = Arc::clone(&inputs);
+        let world = Self::with_job(Some(Box::new(move |_| {
+            let [a, b] = take_inputs(&inputs_clone)?;
+            Ok(f(into_input(a), into_input(b)))
+        })));
+        World {
+            upstream: Some(Upstream {
+                ids: vec![a.to_string(), b.to_string()],
+                cloners: vec![clone_input::<A>, clone_input::<B>],
+                inputs,
+            }),
+            ..world
+        }
+    }
+
+    /// 上流のWorldのIDを引数の順に返します（`after` / `after2` 以外で作成された場合は空）。
+    pub(crate) fn upstream_ids(&self) -> Vec<String> {
+        self.upstream
+            .as_ref()
+            .map(|upstream| upstream.ids.clone())
+            .unwrap_or_default()
+    }
+
+    /// `index` 番目（`upstream_ids()` の順）の上流のWorldの結果を複製し、Jobの引数として渡します。
+    ///
+    /// `type_name` は上流の結果の型の名前です。型が一致しない場合は、そのエラーを保持したうえで返します。
+    pub(crate) fn bind_input(
+        &self,
+        index: usize,
+        value: &(dyn Any + Send),
+        type_name: &'static str,
+    ) -> Result<(), WorldError> {
+        let Some(upstream) = &self.upstream else {
+            return Ok(());
+        };
+        let input = (upstream.cloners[index])(value, type_name);
+        let bound = input.as_ref().map(|_| ()).map_err(WorldError::clone);
+        upstream.inputs.lock().unwrap()[index] = Some(input);
+        bound
+    }
+
+    /// `index` 番目の上流のWorldの結果を既に受け取っていれば、`bind_input` が返した結果を返します。
+    pub(crate) fn input(&self, index: usize) -> Option<Result<(), WorldError>> {
+        let upstream = self.upstream.as_ref()?;
+        let inputs = upstream.inputs.lock().unwrap();
+        let input = inputs.get(index)?.as_ref()?;
+        Some(input.as_ref().map(|_| ()).map_err(WorldError::clone))
+    }
+
+    /// `kill()` でSIGTERMを送ってからSIGKILLを送るまでの猶予期間を設定します。
+    ///
+    /// プロセスベースのWorld（`World::in_process`）にのみ影響します。
+    pub fn with_kill_grace(self, grace: Duration) -> Self {
+        World {
+            kill_grace: grace,
+            ..self
+        }
+    }
+
+    /// 実行開始からの制限時間を設定します。
+    ///
+    /// `start()` から `timeout` が経過しても結果を返さない場合、Worldは `WorldStatus::TimedOut` に遷移し、
+    /// キャンセルトークンがキャンセルされます（プロセスベースのWorldでは子プロセスが強制終了されます）。
+    /// `status()` は制限時間を超えてブロックせず、`Err(WorldError::TimedOut)` を返します。
+    ///
+    /// `Multiverse` で依存先の完了を待つ間（`Blocked`）や、実行器で実行を待つ間（`Queued`）も制限時間に含まれます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{Multiverse, World, WorldError, WorldStatus};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let world = World::with_cancel(|token| {
+    ///     // キャンセルされるまで終わらないタスク
+    ///     while !token.sleep_or_cancel(Duration::from_millis(10)) {}
+    ///     0
+    /// })
+    /// .with_timeout(Duration::from_millis(50));
+    ///
+    /// world.start().unwrap();
+    /// assert_eq!(world.status(), Err(WorldError::TimedOut));
+    /// assert_eq!(world.progress(), WorldStatus::TimedOut);
+    ///
+    /// // 制限時間内に終わるタスクには影響しない
+    /// let quick = World::from(|| { sleep(Duration::from_millis(5)); 1 })
+    ///     .with_timeout(Duration::from_secs(10));
+    /// assert_eq!(quick.run(), Ok(1));
+    ///
+    /// // 依存先が終わらないまま制限時間を過ぎると、実行されずに期限切れになる
+    /// let pw = Multiverse::new();
+    /// pw.add("never".to_string(), World::from(|| ())).unwrap();
+    /// let blocked = World::from(|| 1).with_timeout(Duration::from_millis(50));
+    /// pw.add_with_deps("blocked".to_string(), blocked, &["never"]).unwrap();
+    /// pw.exec("blocked").unwrap();
+    /// assert_eq!(pw.status::<i32>("blocked"), Err(WorldError::TimedOut));
+    /// assert_eq!(pw.progress("blocked").unwrap(), WorldStatus::TimedOut);
+    /// ```
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        World {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// 結果を返さなければならない期限を設定します。
+    ///
+    /// `with_timeout` と同様ですが、実行開始からの相対時間ではなく絶対時刻で指定します。
+    /// 両方が設定されている場合は、早い方が適用されます。
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        World {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// このWorldを実行する実行器を設定します。
+    ///
+    /// 設定しない場合、Worldは `start()` のたびに新しいスレッドで実行されます。
+    /// `Multiverse::with_executor` で作成した `Multiverse` に追加すると、実行器が設定されていないWorldには
+    /// `Multiverse` の実行器が設定されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{ThreadPool, World};
+    /// use std::sync::Arc;
+    ///
+    /// let pool = Arc::new(ThreadPool::new(2));
+    /// let world = World::from(|| 1 + 1).with_executor(pool);
+    /// assert_eq!(world.run(), Ok(2));
+    /// ```
+    pub fn with_executor(self, executor: Arc<dyn Executor>) -> Self {
+        World {
+            executor: Some(executor),
+            ..self
+        }
+    }
+
+    /// 実行を開始するときに、実行スレッドで呼び出されるフックを設定します。
+    ///
+    /// `on_start` / `on_finish` / `on_fail` / `on_stop` のフックは、タスク本体を変更せずに
+    /// 後始末（ロックの解放やファイルのクローズなど）や通知を行うために使えます。
+    /// 終了時のフックは、結果が `status()` などで受け取れるようになる前に呼び出されます。
+    /// フックがパニックしても、Worldの結果には影響しません。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let (start_log, finish_log) = (Arc::clone(&log), Arc::clone(&log));
+    /// let world = World::from(|| 42)
+    ///     .on_start(move || start_log.lock().unwrap().push("start".to_string()))
+    ///     .on_finish(move |result: &i32| finish_log.lock().unwrap().push(format!("finish {result}")));
+    ///
+    /// assert_eq!(world.run(), Ok(42));
+    /// assert_eq!(*log.lock().unwrap(), ["start", "finish 42"]);
+    /// ```
+    pub fn on_start<F>(mut self, f: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.hooks.on_start = Some(Arc::new(f));
+        self
+    }
+
+    /// 正常に完了（`WorldStatus::Finished`）したときに、結果を受け取って実行スレッドで呼び出されるフックを設定します。
+    pub fn on_finish<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&R) + Send + Sync + 'static,
+    {
+        self.hooks.on_finish = Some(Arc::new(f));
+        self
+    }
+
+    /// 失敗（`WorldStatus::Failed`）したときに、失敗の詳細を受け取って実行スレッドで呼び出されるフックを設定します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let message = Arc::new(Mutex::new(String::new()));
+    /// let message_clone = Arc::clone(&message);
+    /// let world = World::try_from(|| "abc".parse::<i32>())
+    ///     .on_fail(move |failure| *message_clone.lock().unwrap() = failure.message.clone());
+    ///
+    /// assert!(world.run().is_err());
+    /// assert_eq!(*message.lock().unwrap(), "invalid digit found in string");
+    /// ```
+    pub fn on_fail<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Failure) + Send + Sync + 'static,
+    {
+        self.hooks.on_fail = Some(Arc::new(f));
+        self
+    }
+
+    /// 実行の開始後に停止・強制終了・期限切れで終了したときに、実行スレッドで呼び出されるフックを設定します。
+    ///
+    /// 実行が始まる前（`Blocked` / `Queued` の間）に停止された場合は呼び出されません。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldError};
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::time::Duration;
+    ///
+    /// let released = Arc::new(AtomicBool::new(false));
+    /// let released_clone = Arc::clone(&released);
+    /// let world = World::with_cancel(|token| token.sleep_or_cancel(Duration::from_secs(10)))
+    ///     .on_stop(move || released_clone.store(true, Ordering::SeqCst));
+    ///
+    /// world.start().unwrap();
+    /// world.stop().unwrap();
+    /// assert!(world.status().is_ok());
+    /// assert!(released.load(Ordering::SeqCst));
+    /// ```
+    pub fn on_stop<F>(mut self, f: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.hooks.on_stop = Some(Arc::new(f));
+        self
+    }
+
+    /// 保持するログの最大行数を設定します（既定は1000行）。超えた分は古い行から捨てられます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    ///
+    /// let world = World::with_logger(|logger| {
+    ///     for i in 0..10 {
+    ///         logger.log(format!("line {i}"));
+    ///     }
+    /// })
+    /// .with_log_capacity(3);
+    ///
+    /// world.run().unwrap();
+    /// let lines: Vec<_> = world.logs().into_iter().map(|line| line.text).collect();
+    /// assert_eq!(lines, ["line 7", "line 8", "line 9"]);
+    /// ```
+    pub fn with_log_capacity(self, capacity: usize) -> Self {
+        self.logs.lock().unwrap().set_capacity(capacity);
+        self
+    }
+
+    /// 実行器で実行を待つ間の優先度を設定します（既定は0で、値が大きいほど先に実行されます）。
+    ///
+    /// 優先度は `ThreadPool` などの実行器がキューから実行するWorldを選ぶときに使われます。
+    /// 実行器が設定されていないWorldは待たずに実行されるため、影響はありません。
+    pub fn with_priority(self, priority: i32) -> Self {
+        self.priority.set(priority);
+        self
+    }
+
+    /// 現在の優先度を取得します。
+    pub fn priority(&self) -> i32 {
+        self.priority.get()
+    }
+
+    /// 優先度を変更します。
+    ///
+    /// 実行器のキューで待っている（`WorldStatus::Queued`）間に変更すると、次に実行するWorldを選ぶときから反映されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    ///
+    /// let world = World::from(|| 1).with_priority(5);
+    /// world.set_priority(-1);
+    /// assert_eq!(world.priority(), -1);
+    /// ```
+    pub fn set_priority(&self, priority: i32) {
+        self.priority.set(priority);
+    }
+
+    /// 状態が変化するたびに呼び出されるリスナーを設定します（既存のリスナーは置き換えられます）。
+    pub(crate) fn set_status_listener(&self, listener: StatusListener) {
+        #[cfg(feature = "tracing")]
+        let listener = traced_listener(&self.span, Some(listener));
+        *self.listener.lock().unwrap() = Some(listener);
+    }
+
+    /// WorldのスパンにIDを記録します。
+    #[cfg(feature = "tracing")]
+    pub(crate) fn set_trace_id(&self, id: &str) {
+        self.span.record("id", id);
+    }
+
+    /// 実行器が設定されていなければ、`executor` を設定します。
+    pub(crate) fn or_executor(self, executor: Option<&Arc<dyn Executor>>) -> Self {
+        match (&self.executor, executor) {
+            (None, Some(executor)) => self.with_executor(Arc::clone(executor)),
+            _ => self,
+        }
+    }
+
+    /// このWorldに紐づいたキャンセルトークンを返します。
+    ///
+    /// 返されたトークンをキャンセルすると、`with_cancel` で渡されたトークンもキャンセルされます。
+    /// `reset()` すると新しいトークンに置き換えられるため、改めて取得し直す必要があります。
+    pub fn cancel_token(&self) -> CancelToken {
+        self.run_state().cancel_token
+    }
+
     /// Worldのプロセスを実行し、終了まで待機します。
     /// このメソッドは現在のスレッドをブロックします。
     ///
     /// # 戻り値
     /// `Ok(R)` - プロセスが正常に完了し、`R`型の値を返した場合。
-    /// `Err(String)` - プロセスが失敗した場合（パニックを含む）、または既に実行中であった場合。
+    /// `Err(WorldError)` - プロセスが失敗した場合（パニックを含む）、または既に実行中であった場合。
     ///
     /// # 例
     /// ```
@@ -166,7 +1358,7 @@ impl<R: Send + 'static> World<R> {
     /// assert!(result_fail.is_err());
     /// assert!(matches!(world_with_panic.progress(), WorldStatus::Failed(_)));
     /// ```
-    pub fn run(&self) -> Result<R, String> {
+    pub fn run(&self) -> Result<R, WorldError> {
         self.start()?; // バックグラウンドで実行開始
         self.status() // 実行終了を待機し、結果を返す
     }
@@ -178,9 +1370,9 @@ impl<R: Send + 'static> World<R> {
     /// プロセス内で発生したパニックは捕捉され、`WorldStatus::Failed`として記録されます。
     ///
     /// # エラー
-    /// * `Err("World is already running.")` - この`World`が既に実行中の場合に返されます。
-    /// * `Err("World has already completed or failed and cannot be restarted.")` - 既に完了または失敗したWorldを再実行しようとした場合に返されます。
-    /// * `Err("No process defined for this World.")` - `World::new()`で作成され、
+    /// * `Err(WorldError::AlreadyRunning)` - この`World`が既に実行中の場合に返されます。
+    /// * `Err(WorldError::AlreadyCompleted)` - 既に完了または失敗したWorldを再実行しようとした場合に返されます。
+    /// * `Err(WorldError::NoProcess)` - `World::new()`で作成され、
     ///   まだ実行する関数が設定されていない`World`に対して呼び出された場合に返されます。
     ///
     /// # 例
@@ -207,58 +1399,180 @@ impl<R: Send + 'static> World<R> {
     /// world.status().unwrap(); // 完了を待つ
     /// assert_eq!(world.progress(), WorldStatus::Finished);
     /// ```
-    pub fn start(&self) -> Result<(), String> {
-        let status_guard = self.status.lock().unwrap();
-        if *status_guard == WorldStatus::Running {
-            return Err("World is already running.".to_string());
+    pub fn start(&self) -> Result<(), WorldError> {
+        let run = self.run_state();
+        let status_guard = run.status.lock();
+        if status_guard.is_active() {
+            return Err(WorldError::AlreadyRunning);
         }
-        if *status_guard == WorldStatus::Finished || matches!(*status_guard, WorldStatus::Failed(_)) {
-            return Err(
-                "World has already completed or failed and cannot be restarted.".to_string(),
-            );
+        if matches!(
+            *status_guard,
+            WorldStatus::Finished | WorldStatus::Failed(_) | WorldStatus::Skipped
+        ) {
+            return Err(WorldError::AlreadyCompleted);
+        }
+        self.launch(&run, status_guard)
+    }
+
+    /// `Ready` 状態のWorldを、依存するWorldの完了を待つ `Blocked` 状態にします。
+    ///
+    /// `Ready` 以外の状態であれば何もせず `false` を返します。
+    pub(crate) fn block(&self) -> bool {
+        let run = self.run_state();
+        let mut status_guard = run.status.lock();
+        if *status_guard != WorldStatus::Ready {
+            return false;
+        }
+        *status_guard = WorldStatus::Blocked;
+        drop(status_guard);
+        // 依存先を待つ間も期限を過ぎれば期限切れにする
+        self.spawn_watchdog(&run);
+        true
+    }
+
+    /// `Blocked` 状態のWorldを実行開始します。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NotRunning)` - 待機中に停止されるなどして、`Blocked` 状態でなくなっていた場合に返されます。
+    pub(crate) fn unblock(&self) -> Result<(), WorldError> {
+        let run = self.run_state();
+        let status_guard = run.status.lock();
+        if *status_guard != WorldStatus::Blocked {
+            return Err(WorldError::NotRunning);
+        }
+        self.launch(&run, status_guard)
+    }
+
+    /// `Blocked` 状態のWorldを、実行せずに `status`（`Skipped` や `Failed`）で終了させます。
+    ///
+    /// `status()` は `status` に対応するエラーを返すようになります。`Blocked` 状態でなければ何もしません。
+    pub(crate) fn abandon(&self, status: WorldStatus) {
+        let run = self.run_state();
+        let mut status_guard = run.status.lock();
+        if *status_guard != WorldStatus::Blocked {
+            return;
+        }
+        let error = self.status_error(&status);
+        *status_guard = status;
+        drop(status_guard);
+
+        self.process.lock().unwrap().take();
+        if let Some(sender) = self.result_sender.lock().unwrap().take() {
+            let _ = sender.send(Err(error));
         }
+        run.completion.fire();
+    }
+
+    /// 実行スレッドを起動する共通処理です。`status_guard` は起動してよい状態であることを確認済みのものです。
+    fn launch(&self, run: &RunState, mut status_guard: StatusGuard<'_>) -> Result<(), WorldError> {
+        let Some(job) = self.process.lock().unwrap().take() else {
+            return Err(WorldError::NoProcess);
+        };
+        // Futureは、実行器が設定されていなければ組み込みの実行器で実行する
+        let executor = match (&job, &self.executor) {
+            (_, Some(executor)) => Some(Arc::clone(executor)),
+            (Job::Future(_), None) => Some(executor::builtin()),
+            (Job::Blocking(_), None) => None,
+        };
+        let runner = JobRunner {
+            status: Arc::clone(&run.status),
+            panic_payload: Arc::clone(&run.panic_payload),
+            sender_slot: Arc::clone(&self.result_sender),
+            completion: run.completion.clone(),
+            hooks: self.hooks.clone(),
+            timings: Arc::clone(&run.timings),
+            result_taps: Arc::clone(&run.result_taps),
+        };
+        // スレッドで直接実行する場合は、起動前に送信側を受け取っておく（直後にstop()されても結果を返せるように）
+        let result_sender = match &executor {
+            Some(_) => None,
+            None => Some(
+                self.result_sender
+                    .lock()
+                    .unwrap()
+                    .take()
+                    .ok_or(WorldError::NoProcess)?,
+            ),
+        };
 
-        let mut process_guard = self.process.lock().unwrap();
-        let process_opt = process_guard.take();
+        // スレッドの起動を待たずにRunning（実行器の場合はQueued）にしておく（直後のstop()/kill()を受け付けるため）
+        *status_guard = match &executor {
+            Some(_) => WorldStatus::Queued,
+            None => WorldStatus::Running,
+        };
+        drop(status_guard);
+        let mut timings = run.timings.lock().unwrap();
+        match &executor {
+            Some(_) => timings.queued_at = Some(Instant::now()),
+            None => timings.started_at = Some(Instant::now()),
+        }
+        drop(timings);
 
-        if let Some(process_fn) = process_opt {
-            let status_clone = Arc::clone(&self.status);
-            let result_sender_opt = self.result_sender.lock().unwrap().take();
+        // 期限が設定されていれば、監視スレッドを起動する（依存待ちを経た場合は起動済み）
+        self.spawn_watchdog(run);
+        let ctx = JobContext {
+            status: Arc::clone(&run.status),
+            cancel_token: run.cancel_token.clone(),
+            report: Arc::clone(&run.report),
+            logs: Arc::clone(&self.logs),
+        };
 
-            if result_sender_opt.is_none() {
-                return Err("Internal error: Result sender not available.".to_string());
+        match job {
+            Job::Blocking(process_fn) => {
+                #[cfg(feature = "tracing")]
+                let span = self.span.clone();
+                let task = move || {
+                    // クロージャ内で記録したログが、このWorldのものとわかるようにする
+                    #[cfg(feature = "tracing")]
+                    let _entered = span.enter();
+                    let Some(result_sender) = result_sender.or_else(|| runner.begin()) else {
+                        return;
+                    };
+                    runner.hooks.call(&runner.hooks.on_start, |f| f());
+                    let outcome = runner.catch_panic(|| process_fn(&ctx));
+                    runner.finish(Some(outcome), result_sender);
+                };
+                match executor {
+                    Some(executor) => {
+                        executor.execute_with_priority(Box::new(task), self.priority.clone())
+                    }
+                    None => *self.thread_handle.lock().unwrap() = Some(thread::spawn(task)),
+                }
             }
-            let result_sender = result_sender_opt.unwrap();
-
-            let handle = thread::spawn(move || {
-                let mut s = status_clone.lock().unwrap();
-                *s = WorldStatus::Running;
-                drop(s);
-
-                let result = match std::panic::catch_unwind(AssertUnwindSafe(process_fn)) {
-                    Ok(val) => {
-                        let mut s = status_clone.lock().unwrap();
-                        if *s != WorldStatus::Stopped { // Stoppedが設定されていなければFinished
-                            *s = WorldStatus::Finished;
+            Job::Future(mut future) => {
+                let task = async move {
+                    let Some(result_sender) = runner.begin() else {
+                        return;
+                    };
+                    runner.hooks.call(&runner.hooks.on_start, |f| f());
+                    let mut registered = false;
+                    let outcome = future::poll_fn(|cx| {
+                        // 停止・強制終了・期限切れになったら、Futureを破棄して終了する
+                        if ctx.cancel_token.is_cancelled() {
+                            return Poll::Ready(None);
                         }
-                        Ok(val)
-                    }
-                    Err(e) => {
-                        let err_msg = format!("Thread panicked: {:?}", e);
-                        let mut s = status_clone.lock().unwrap();
-                        *s = WorldStatus::Failed(err_msg.clone());
-                        Err(err_msg)
-                    }
+                        if !registered {
+                            registered = true;
+                            let waker = cx.waker().clone();
+                            ctx.cancel_token.on_cancel(move || waker.wake());
+                        }
+                        match runner.catch_panic(|| Ok(future.as_mut().poll(cx))) {
+                            Ok(Poll::Pending) => Poll::Pending,
+                            Ok(Poll::Ready(val)) => Poll::Ready(Some(Ok(val))),
+                            Err(failure) => Poll::Ready(Some(Err(failure))),
+                        }
+                    })
+                    .await;
+                    drop(future);
+                    runner.finish(outcome, result_sender);
                 };
-                let _ = result_sender.send(result);
-            });
-
-            let mut thread_handle_guard = self.thread_handle.lock().unwrap();
-            *thread_handle_guard = Some(handle);
-            Ok(())
-        } else {
-            Err("No process defined for this World.".to_string())
+                #[cfg(feature = "tracing")]
+                let task = tracing::Instrument::instrument(task, self.span.clone());
+                let executor = executor.expect("futures always have an executor");
+                executor::spawn_future(executor, self.priority.clone(), Box::pin(task));
+            }
         }
+        Ok(())
     }
 
     /// Worldのプロセスを停止します（ベストエフォート）。
@@ -266,15 +1580,14 @@ impl<R: Send + 'static> World<R> {
     /// Rustの標準ライブラリの`std::thread`には、実行中のスレッドを外部から
     /// 強制的に停止させる安全なメカニズムは提供されていません。
     /// したがって、このメソッドは`WorldStatus`を`Stopped`に設定し、
-    /// スレッドハンドルを解放（デタッチ）します。
+    /// Worldのキャンセルトークンをキャンセルしたうえで、スレッドハンドルを解放（デタッチ）します。
     ///
     /// 実行中のタスク（クロージャ）がこの停止シグナルに応答するには、
-    /// クロージャ内で定期的に停止フラグをチェックし、
-    /// `WorldStatus::Stopped`になった場合に自ら終了するような
-    /// 協調的な停止メカニズムを実装する必要があります。
+    /// `World::with_cancel` で受け取った `CancelToken` を定期的にチェックするなど、
+    /// 自ら終了するような協調的な停止メカニズムを実装する必要があります。
     ///
     /// # エラー
-    /// * `Err("World is not running or already stopped.")` - `World`が実行中でない場合に返されます。
+    /// * `Err(WorldError::NotRunning)` - `World`が実行中でない場合に返されます。
     ///
     /// # 例
     /// ```
@@ -312,14 +1625,19 @@ impl<R: Send + 'static> World<R> {
     /// assert!(result.is_ok()); // 協調的に終了すればOk
     /// assert_eq!(world.progress(), WorldStatus::Stopped); // 協調的停止によりStopped
     /// ```
-    pub fn stop(&self) -> Result<(), String> {
-        let mut status_guard = self.status.lock().unwrap();
-        if *status_guard != WorldStatus::Running {
-            return Err("World is not running or already stopped.".to_string());
+    pub fn stop(&self) -> Result<(), WorldError> {
+        let run = self.run_state();
+        let mut status_guard = run.status.lock();
+        if !status_guard.is_active() {
+            return Err(WorldError::NotRunning);
         }
+        let was_waiting = matches!(*status_guard, WorldStatus::Blocked | WorldStatus::Queued);
         *status_guard = WorldStatus::Stopped;
         drop(status_guard); // ロックを早期に解放
 
+        // 協調的な停止を要求する（on_cancelコールバックはここで実行される）
+        run.cancel_token.cancel();
+
         // スレッドハンドルをNoneにするが、joinはしない。これにより、stop()はブロックしない。
         // スレッド自体が協調的に終了するか、外部からstatus()でjoinされるのを待つ。
         let mut handle_guard = self.thread_handle.lock().unwrap();
@@ -328,20 +1646,267 @@ impl<R: Send + 'static> World<R> {
         // result_senderをドロップし、これにより受信側でRecvErrorが発生するようにできる
         let _ = self.result_sender.lock().unwrap().take();
 
+        // 依存待ち・実行待ちのまま停止された場合は結果が送られないため、ここで完了を通知する
+        if was_waiting {
+            run.completion.fire();
+        }
+
+        Ok(())
+    }
+
+    /// Worldを強制終了します。
+    ///
+    /// `World::in_process` で作成されたプロセスベースのWorldでは、子プロセスにSIGTERMを送り、
+    /// 猶予期間内に終了しなければSIGKILLを送って回収し、状態を`WorldStatus::Killed`にします。
+    /// 猶予期間の待機と回収はバックグラウンドで行われるため、このメソッドはブロックしません。
+    /// `stop()` 済みでも子プロセスがまだ終了していなければ強制終了できます。
+    ///
+    /// スレッドで実行されるWorldは外部から強制終了できないため、`stop()` と同じ動作になります。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::NotRunning)` - `World`が実行中でない場合に返されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldStatus};
+    /// use std::process::Command;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// // SIGTERMを無視するプロセスも、猶予期間の後にSIGKILLで強制終了される
+    /// let mut cmd = Command::new("sh");
+    /// cmd.args(["-c", "trap '' TERM; exec sleep 30"]);
+    /// let world = World::in_process(cmd).with_kill_grace(Duration::from_millis(100));
+    ///
+    /// world.start().unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// world.kill().unwrap();
+    ///
+    /// assert!(world.status().is_err()); // 強制終了されたWorldは結果を返さない
+    /// assert_eq!(world.progress(), WorldStatus::Killed);
+    /// ```
+    pub fn kill(&self) -> Result<(), WorldError> {
+        let Some(slot) = &self.child else {
+            return self.stop();
+        };
+
+        let run = self.run_state();
+        let mut status_guard = run.status.lock();
+        if matches!(*status_guard, WorldStatus::Blocked | WorldStatus::Queued) {
+            // 子プロセスはまだ起動していない
+            drop(status_guard);
+            return self.stop();
+        }
+        let child_alive = slot
+            .lock()
+            .unwrap()
+            .as_mut()
+            .is_none_or(|child| matches!(child.try_wait(), Ok(None)));
+        let killable = match *status_guard {
+            WorldStatus::Running | WorldStatus::Retrying { .. } => true,
+            WorldStatus::Stopped => child_alive,
+            _ => false,
+        };
+        if !killable {
+            return Err(WorldError::NotRunning);
+        }
+        *status_guard = WorldStatus::Killed;
+        drop(status_guard);
+
+        run.cancel_token.cancel();
+
+        let slot = Arc::clone(slot);
+        let grace = self.kill_grace;
+        thread::spawn(move || process::kill_with_grace(&slot, grace));
+        Ok(())
+    }
+
+    /// 完了・失敗・停止したWorldを `Ready` 状態に戻し、再実行できるようにします。
+    ///
+    /// 前回の実行の最終状態は `history()` に記録されます。
+    /// 結果チャネル、キャンセルトークン、期限はすべて新しい実行のために作り直されるため、
+    /// 前回の結果のうち取得されていないものは破棄されます。
+    /// 停止した前回の実行のスレッドがまだ動いていても、新しい実行には影響しません。
+    /// `Ready` 状態のWorldに対しては何もしません。
+    ///
+    /// # エラー
+    /// * `Err(WorldError::AlreadyRunning)` - `World`が実行中の場合に返されます。
+    /// * `Err(WorldError::NotRepeatable)` - `World`が `repeatable` 以外で作成されていて、既に実行された場合に返されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::{World, WorldError, WorldStatus};
+    ///
+    /// let world = World::repeatable(|| "done");
+    /// world.run().unwrap();
+    /// world.reset().unwrap();
+    /// assert_eq!(world.progress(), WorldStatus::Ready);
+    /// assert_eq!(world.history(), vec![WorldStatus::Finished]);
+    ///
+    /// // FnOnceから作成されたWorldは再実行できない
+    /// let once = World::from(|| "done");
+    /// once.run().unwrap();
+    /// assert_eq!(once.reset(), Err(WorldError::NotRepeatable));
+    /// ```
+    pub fn reset(&self) -> Result<(), WorldError> {
+        let mut run = self.run.lock().unwrap();
+        let previous = run.status.lock().clone();
+        if previous == WorldStatus::Ready {
+            return Ok(());
+        }
+        if previous.is_active() {
+            return Err(WorldError::AlreadyRunning);
+        }
+        let Some(factory) = &self.factory else {
+            return Err(WorldError::NotRepeatable);
+        };
+
+        // 前回の実行のスレッドや監視スレッドは古い状態を保持したまま終了する
+        *run = RunState::new(&self.listener);
+        let listener = self.listener.lock().unwrap().clone();
+        if let Some(listener) = listener {
+            listener(&previous, &WorldStatus::Ready);
+        }
+        *self.process.lock().unwrap() = Some(Job::Blocking(factory()));
+        let (tx, rx) = mpsc::channel();
+        *self.result_sender.lock().unwrap() = Some(tx);
+        *self.result_receiver.lock().unwrap() = Some(rx);
+        self.thread_handle.lock().unwrap().take();
+        *self.run_deadline.lock().unwrap() = None;
+        self.history.lock().unwrap().push(previous);
         Ok(())
     }
 
+    /// これまでの実行の最終状態を古い順に返します（現在の実行は含みません）。
+    ///
+    /// `reset()` されるたびに、その時点の状態（`Finished`、`Failed` など）が追加されます。
+    pub fn history(&self) -> Vec<WorldStatus> {
+        self.history.lock().unwrap().clone()
+    }
+
+    /// `Ready` から抜けるとき（依存待ち・実行待ち・実行中になるとき）に期限を確定し、期限切れを監視するスレッドを起動します。
+    ///
+    /// 期限が設定されていない場合や、現在の実行の監視スレッドが既に起動している場合は何もしません。
+    /// 監視スレッドは、Worldが完了する（`completion` が発火する）か期限が来ると終了します。
+    fn spawn_watchdog(&self, run: &RunState) {
+        let mut deadline_guard = self.run_deadline.lock().unwrap();
+        if deadline_guard.is_some() {
+            return; // 依存待ちになったときに起動済み
+        }
+        let timeout_deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let deadline = match (self.deadline, timeout_deadline) {
+            (Some(a), Some(b)) => a.min(b),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => return,
+        };
+        *deadline_guard = Some(deadline);
+        drop(deadline_guard);
+
+        let status = Arc::clone(&run.status);
+        let cancel_token = run.cancel_token.clone();
+        let child = self.child.clone();
+        let grace = self.kill_grace;
+        let completion = run.completion.clone();
+        thread::spawn(move || {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !completion.wait_timeout(remaining) {
+                time_out(&status, &cancel_token, child, grace, &completion);
+            }
+        });
+    }
+
+    /// 現在の実行で報告された進捗を取得します（`with_reporter` を参照）。
+    ///
+    /// 進捗を報告しないWorldや、まだ報告していないWorldでは、すべての項目が `None` です。
+    /// `reset()` すると、報告された進捗は破棄されます。
+    pub fn report(&self) -> Report {
+        self.run_state().report.lock().unwrap().clone()
+    }
+
+    /// Worldが出力したログのうち、バッファに残っている行を古い順に取得します。
+    ///
+    /// `with_logger` の `Logger` で記録した行と、`in_process` の子プロセスの標準出力・標準エラー出力の行が含まれます。
+    /// ログは `reset()` しても破棄されないため、再実行されたWorldでは前回の実行の行も含まれます。
+    pub fn logs(&self) -> Vec<LogLine> {
+        self.logs.lock().unwrap().lines()
+    }
+
+    /// Worldが出力したログを、出力されるそばから受け取るレシーバーを返します。
+    ///
+    /// バッファに残っている行を受け取った後、新しい行を受け取り続けます。
+    /// 現在の実行が完了（停止などを含む）すると、レシーバーの反復は終わります。
+    /// 既に完了しているWorldでは、バッファに残っている行だけを受け取ります。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let world = World::with_logger(|logger| {
+    ///     for i in 0..3 {
+    ///         logger.log(format!("tick {i}"));
+    ///         sleep(Duration::from_millis(10));
+    ///     }
+    /// });
+    /// world.start().unwrap();
+    ///
+    /// let lines: Vec<_> = world.follow_logs().into_iter().map(|line| line.text).collect();
+    /// assert_eq!(lines, ["tick 0", "tick 1", "tick 2"]);
+    /// ```
+    pub fn follow_logs(&self) -> mpsc::Receiver<LogLine> {
+        let receiver = self.logs.lock().unwrap().follow();
+        let logs = Arc::clone(&self.logs);
+        // 既に完了していれば、すぐに呼び出される
+        self.on_complete(move || logs.lock().unwrap().close_followers());
+        receiver
+    }
+
+    /// 現在の実行にかかった時間の記録を取得します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let world = World::from(|| sleep(Duration::from_millis(20)));
+    /// assert_eq!(world.metrics().started_at, None);
+    ///
+    /// world.run().unwrap();
+    /// let metrics = world.metrics();
+    /// assert!(metrics.duration().unwrap() >= Duration::from_millis(20));
+    /// assert_eq!(metrics.queued_at, None); // 実行器を使わない場合は、待たずに開始される
+    /// if let Some(cpu_time) = metrics.cpu_time {
+    ///     // 眠っている間はCPUを消費しない
+    ///     assert!(cpu_time < metrics.duration().unwrap());
+    /// }
+    /// ```
+    pub fn metrics(&self) -> WorldMetrics {
+        let timings = self.run_state().timings.lock().unwrap().clone();
+        WorldMetrics {
+            created_at: self.created_at,
+            queued_at: timings.queued_at,
+            started_at: timings.started_at,
+            finished_at: timings.finished_at,
+            cpu_time: timings.cpu_time,
+        }
+    }
+
     /// Worldの実行状態を取得します。
     pub fn progress(&self) -> WorldStatus {
-        self.status.lock().unwrap().clone()
+        self.run_state().status.lock().clone()
     }
 
     /// 実行終了まで待機し、成功したか失敗したかなどの値を返します。
     ///
+    /// 期限（`with_timeout` / `with_deadline`）が設定されている場合、期限を超えて待機することはありません。
+    /// 期限までに結果が得られなければ、Worldを`WorldStatus::TimedOut`に遷移させて`Err(WorldError::TimedOut)`を返します。
+    ///
     /// # 戻り値
     /// `Ok(R)` - プロセスが正常に完了し、`R`型の値を返した場合。
-    /// `Err(String)` - プロセスが失敗した場合、またはWorldが見つからない場合。
-    ///                 また、Worldが停止されたり、結果が取得できなかったりした場合も`Err`。
+    /// `Err(WorldError)` - プロセスが失敗した場合。
+    ///                 また、Worldが停止されたり、期限切れになったり、結果が取得できなかったりした場合も`Err`。
     ///
     /// # 例
     /// ```
@@ -361,57 +1926,389 @@ impl<R: Send + 'static> World<R> {
     /// assert!(matches!(fail_world.progress(), WorldStatus::Failed(_)));
     /// println!("Error from failed world: {}", result.unwrap_err());
     /// ```
-    pub fn status(&self) -> Result<R, String> {
-        let mut handle_guard = self.thread_handle.lock().unwrap();
-        if let Some(handle) = handle_guard.take() {
-            // スレッドが完了するまで待機
-            // ここでスレッドがパニックした場合、Errが返る
-            handle
-                .join()
-                .map_err(|e| format!("Thread panicked: {:?}", e))?;
+    pub fn status(&self) -> Result<R, WorldError> {
+        // 期限が設定されていれば、期限までしか待たない（期限が来ると期限切れのエラーが返る）
+        let deadline = *self.run_deadline.lock().unwrap();
+        loop {
+            if let Some(result) = self.wait_result(deadline) {
+                return result;
+            }
         }
+    }
 
-        // スレッドが終了した後、チャネルから結果を受け取る
-        let mut receiver_opt = self.result_receiver.lock().unwrap();
-        if let Some(receiver) = receiver_opt.take() {
-            match receiver.recv() {
-                Ok(task_result) => task_result, // タスク自体が返したResult<R, String>
-                Err(_) => {
-                    // 送信側がドロップされたか、メッセージが送信されなかった場合
-                    let current_status = self.status.lock().unwrap().clone();
-                    match current_status {
-                        WorldStatus::Finished => {
-                            Err("World finished but result not sent (internal error).".to_string())
-                        }
-                        WorldStatus::Failed(e) => Err(e),
-                        WorldStatus::Stopped => {
-                            Err("World was stopped before completion.".to_string())
-                        }
-                        WorldStatus::Killed => {
-                            Err("World was killed before completion.".to_string())
-                        }
-                        _ => Err(format!(
-                            "World ended with unexpected status and no result: {}",
-                            current_status
-                        )),
-                    }
+    /// 結果が得られると完了する `Future` を返します。
+    ///
+    /// `status()` と同じ結果になりますが、スレッドをブロックせずに待機します。
+    /// 特定の非同期ランタイムには依存せず、Worldの完了時に `Waker` で通知されます。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::future::Future;
+    /// use std::sync::Arc;
+    /// use std::task::{Context, Poll, Wake};
+    /// use std::thread::{self, Thread};
+    ///
+    /// // 最小限の実行器（通常はtokioなどの非同期ランタイムを使います）
+    /// struct ThreadWaker(Thread);
+    /// impl Wake for ThreadWaker {
+    ///     fn wake(self: Arc<Self>) {
+    ///         self.0.unpark();
+    ///     }
+    /// }
+    /// fn block_on<F: Future>(future: F) -> F::Output {
+    ///     let mut future = std::pin::pin!(future);
+    ///     let waker = Arc::new(ThreadWaker(thread::current())).into();
+    ///     let mut cx = Context::from_waker(&waker);
+    ///     loop {
+    ///         match future.as_mut().poll(&mut cx) {
+    ///             Poll::Ready(output) => return output,
+    ///             Poll::Pending => thread::park(),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let world = World::from(|| 6 * 7);
+    /// world.start().unwrap();
+    /// assert_eq!(block_on(async { world.join().await }), Ok(42));
+    /// ```
+    ///
+    /// 待機中に `reset` されて再実行された場合は、新しい実行の結果を待ちます。
+    /// ```
+    /// # use std::future::Future;
+    /// # use std::sync::Arc;
+    /// # use std::task::{Context, Poll, Wake};
+    /// # use std::thread::{self, Thread};
+    /// # struct ThreadWaker(Thread);
+    /// # impl Wake for ThreadWaker {
+    /// #     fn wake(self: Arc<Self>) {
+    /// #         self.0.unpark();
+    /// #     }
+    /// # }
+    /// # fn block_on<F: Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let waker = Arc::new(ThreadWaker(thread::current())).into();
+    /// #     let mut cx = Context::from_waker(&waker);
+    /// #     loop {
+    /// #         match future.as_mut().poll(&mut cx) {
+    /// #             Poll::Ready(output) => return output,
+    /// #             Poll::Pending => thread::park(),
+    /// #         }
+    /// #     }
+    /// # }
+    /// use parallel_world::World;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::time::Duration;
+    ///
+    /// let runs = AtomicUsize::new(0);
+    /// let world = World::repeatable(move || {
+    ///     thread::sleep(Duration::from_millis(100));
+    ///     runs.fetch_add(1, Ordering::SeqCst) + 1
+    /// });
+    /// world.start().unwrap();
+    /// thread::scope(|s| {
+    ///     s.spawn(|| {
+    ///         thread::sleep(Duration::from_millis(30));
+    ///         world.stop().unwrap();
+    ///         world.reset().unwrap();
+    ///         world.start().unwrap();
+    ///     });
+    ///     assert_eq!(block_on(world.join()), Ok(2));
+    /// });
+    /// ```
+    pub fn join(&self) -> impl Future<Output = Result<R, WorldError>> + '_ {
+        let mut waker = None;
+        future::poll_fn(move |cx| {
+            cancel::poll_completion(
+                cx,
+                &mut waker,
+                || self.history.lock().unwrap().len(),
+                || self.try_status(),
+                |callback| self.on_complete(callback),
+            )
+        })
+    }
+
+    /// 結果が既に得られていれば、ブロックせずにそれを返します。
+    ///
+    /// # 戻り値
+    /// `None` - Worldがまだ結果を返していない場合（未実行の場合を含む）。
+    /// `Some(result)` - `status()` が返すのと同じ結果。一度結果を取得すると、以降は`status()`と同様にエラーになります。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let world = World::from(|| { sleep(Duration::from_millis(50)); 7 });
+    /// world.start().unwrap();
+    /// assert_eq!(world.try_status(), None); // まだ実行中
+    ///
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(world.try_status(), Some(Ok(7)));
+    /// ```
+    pub fn try_status(&self) -> Option<Result<R, WorldError>> {
+        self.collect()
+    }
+
+    /// 最大 `timeout` の間だけ結果を待機します。
+    ///
+    /// # 戻り値
+    /// `None` - `timeout` が経過してもWorldが結果を返さなかった場合。Worldはそのまま実行を続けます。
+    /// `Some(result)` - `status()` が返すのと同じ結果。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::World;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let world = World::from(|| { sleep(Duration::from_millis(100)); "done" });
+    /// world.start().unwrap();
+    /// assert_eq!(world.status_timeout(Duration::from_millis(10)), None);
+    /// assert_eq!(world.status_timeout(Duration::from_secs(10)), Some(Ok("done")));
+    /// ```
+    pub fn status_timeout(&self, timeout: Duration) -> Option<Result<R, WorldError>> {
+        let mut limit = Instant::now() + timeout;
+        if let Some(deadline) = *self.run_deadline.lock().unwrap() {
+            limit = limit.min(deadline);
+        }
+        self.wait_result(Some(limit))
+    }
+
+    /// 結果が得られるか、`limit` を過ぎるまで待機します（`None` の場合は結果が得られるまで待ちます）。
+    ///
+    /// 結果チャネルのロックを保持したまま待たないよう、完了の通知を待ってから結果を受け取ります。
+    fn wait_result(&self, limit: Option<Instant>) -> Option<Result<R, WorldError>> {
+        loop {
+            // reset() されると完了の通知も作り直されるため、毎回取得し直す
+            let completion = self.run_state().completion;
+            match limit {
+                Some(limit) => {
+                    completion.wait_timeout(limit.saturating_duration_since(Instant::now()));
                 }
+                None => completion.wait(),
+            }
+            if let Some(result) = self.collect() {
+                return Some(result);
             }
-        } else {
+            if limit.is_some_and(|limit| Instant::now() >= limit) {
+                return None;
+            }
+        }
+    }
+
+    /// 結果を受け取るための共通処理です。
+    ///
+    /// 結果チャネルから受信を試み、結果が得られたか、結果が得られないことが確定した場合は`Some`を返します。
+    /// まだ結果が送られておらず、期限切れでもない場合は `None` を返します（チャネルは保持されたまま）。
+    fn collect(&self) -> Option<Result<R, WorldError>> {
+        let mut receiver_opt = self.result_receiver.lock().unwrap();
+        let Some(receiver) = receiver_opt.as_ref() else {
             // result_receiverが既にtakeされていた場合（status()が複数回呼ばれたなど）
-            let current_status = self.status.lock().unwrap().clone();
-            match current_status {
-                WorldStatus::Finished => Err("World result already retrieved.".to_string()),
-                WorldStatus::Failed(e) => Err(e),
-                _ => Err(format!(
-                    "World result not available for status: {}",
-                    current_status
-                )),
+            let error = match self.progress() {
+                WorldStatus::Finished => WorldError::ResultAlreadyTaken,
+                current_status => self.status_error(&current_status),
+            };
+            return Some(Err(error));
+        };
+
+        match receiver.try_recv() {
+            Ok(task_result) => {
+                receiver_opt.take();
+                drop(receiver_opt);
+                // 結果は送信済みなので、スレッドの終了はすぐに待てる
+                // （完了通知のコールバックから、実行スレッド自身が取り出す場合は待たない）
+                let handle = self.thread_handle.lock().unwrap().take();
+                if let Some(handle) = handle
+                    && handle.thread().id() != thread::current().id()
+                    && let Err(e) = handle.join()
+                {
+                    return Some(Err(WorldError::Panicked {
+                        payload: failure::payload_message(e.as_ref()),
+                        backtrace: None,
+                    }));
+                }
+                Some(task_result) // タスク自体が返したResult<R, WorldError>
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                let deadline = *self.run_deadline.lock().unwrap();
+                let expired = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+                let run = self.run_state();
+                if !expired && *run.status.lock() != WorldStatus::TimedOut {
+                    return None;
+                }
+                // 期限切れ。スレッドは終わっていない可能性があるため、joinせずに手放す
+                receiver_opt.take();
+                drop(receiver_opt);
+                let _ = self.thread_handle.lock().unwrap().take();
+                time_out(
+                    &run.status,
+                    &run.cancel_token,
+                    self.child.clone(),
+                    self.kill_grace,
+                    &run.completion,
+                );
+                Some(Err(WorldError::TimedOut))
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                receiver_opt.take();
+                drop(receiver_opt);
+                // 送信側がドロップされたか、メッセージが送信されなかった場合
+                Some(Err(self.status_error(&self.progress())))
+            }
+        }
+    }
+
+    /// 結果が得られない場合に、現在の状態に対応するエラーを返します。
+    fn status_error(&self, status: &WorldStatus) -> WorldError {
+        match status {
+            WorldStatus::Ready => WorldError::NotRunning,
+            WorldStatus::Running
+            | WorldStatus::Retrying { .. }
+            | WorldStatus::Blocked
+            | WorldStatus::Queued => WorldError::AlreadyRunning,
+            WorldStatus::Finished => WorldError::ResultAlreadyTaken,
+            WorldStatus::Failed(failure) => failure_error(failure),
+            WorldStatus::Stopped => WorldError::Stopped,
+            WorldStatus::Skipped => WorldError::Skipped,
+            WorldStatus::TimedOut => WorldError::TimedOut,
+            WorldStatus::Killed => WorldError::Killed,
+        }
+    }
+
+    /// パニックで失敗した場合に、`panic!` に渡された元のペイロードを取り出します。
+    ///
+    /// 取り出したペイロードを `std::panic::resume_unwind` に渡すと、呼び出し側のスレッドでパニックを再開できます。
+    /// ペイロードは一度だけ取り出せます。パニックしていない場合や、既に取り出した場合は`None`を返します。
+    ///
+    /// # 例
+    /// ```
+    /// use parallel_world::failure::payload_message;
+    /// use parallel_world::{World, WorldError, WorldStatus};
+    /// use std::panic::{self, AssertUnwindSafe};
+    ///
+    /// let world = World::from(|| -> i32 { panic!("disk full: {}", "/var") });
+    /// let err = world.run().unwrap_err();
+    /// assert!(matches!(err, WorldError::Panicked { ref payload, .. } if payload == "disk full: /var"));
+    ///
+    /// // 状態には、メッセージとパニックの発生位置が記録される
+    /// let WorldStatus::Failed(failure) = world.progress() else { panic!() };
+    /// assert_eq!(failure.message, "disk full: /var");
+    /// assert!(failure.location.is_some());
+    ///
+    /// // 元のペイロードを取り出して、呼び出し側でパニックを再開できる
+    /// let payload = world.take_panic_payload().unwrap();
+    /// let resumed = panic::catch_unwind(AssertUnwindSafe(|| panic::resume_unwind(payload)));
+    /// assert_eq!(payload_message(resumed.unwrap_err().as_ref()), "disk full: /var");
+    /// assert!(world.take_panic_payload().is_none());
+    /// ```
+    pub fn take_panic_payload(&self) -> Option<Box<dyn Any + Send>> {
+        self.run_state().panic_payload.lock().unwrap().take()
+    }
+
+    /// 結果が受け取れる状態になったときに一度だけ呼び出されるコールバックを登録します。
+    ///
+    /// 既に結果が受け取れる状態であれば、コールバックは即座に実行されます。
+    pub(crate) fn on_complete<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.run_state().completion.on_fire(callback);
+    }
+
+    /// 現在の実行が `Finished` になったときに、結果が送られる前にその結果を参照する `tap` を登録します。
+    ///
+    /// 結果が既に送られている（送られる直前を含む）場合は登録せずに `false` を返します。
+    /// `Finished` 以外で終了した場合、`tap` は呼び出されません。
+    pub(crate) fn on_result(&self, tap: ResultTap) -> bool {
+        match self.run_state().result_taps.lock().unwrap().as_mut() {
+            Some(taps) => {
+                taps.push(tap);
+                true
             }
+            None => false,
+        }
+    }
+}
+
+/// 失敗を対応する`WorldError`に変換します。
+///
+/// クロージャが返したエラーによる失敗は`WorldError::Failed`に、パニックによる失敗は`WorldError::Panicked`になります。
+fn failure_error(failure: &Failure) -> WorldError {
+    if failure.is_error() {
+        WorldError::Failed(failure.clone())
+    } else {
+        WorldError::Panicked {
+            payload: failure.message.clone(),
+            backtrace: failure.backtrace.clone(),
         }
     }
 }
 
+/// 上流の結果を期待される型 `T` にダウンキャストし、複製します。
+fn clone_input<T: Clone + Send + 'static>(
+    value: &(dyn Any + Send),
+    actual: &'static str,
+) -> Result<Box<dyn Any + Send>, WorldError> {
+    match value.downcast_ref::<T>() {
+        Some(value) => Ok(Box::new(value.clone())),
+        None => Err(WorldError::DowncastFailed {
+            expected: std::any::type_name::<T>(),
+            actual,
+        }),
+    }
+}
+
+/// `clone_input::<T>` で複製された上流の結果を取り出します。
+fn into_input<T: 'static>(value: Box<dyn Any + Send>) -> T {
+    *value.downcast().expect("input type was checked when bound")
+}
+
+/// `bind_input` で渡された上流の結果を、すべて取り出します。
+fn take_inputs<const N: usize>(
+    inputs: &Mutex<Vec<InputSlot>>,
+) -> Result<[Box<dyn Any + Send>; N], Failure> {
+    let mut inputs = inputs.lock().unwrap();
+    if inputs.iter().any(Option::is_none) {
+        return Err(Failure::from_error(WorldError::InputsNotProvided));
+    }
+    let values = inputs
+        .iter_mut()
+        .map(|input| input.take().expect("input was checked"))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(Failure::from_error)?;
+    Ok(values
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("one input per upstream World")))
+}
+
+/// 期限切れになったWorldを`TimedOut`に遷移させ、キャンセルと完了を通知します。
+///
+/// 既に完了・停止しているWorldには何もしません。
+/// プロセスベースのWorldでは、子プロセスをバックグラウンドで強制終了します。
+fn time_out(
+    status: &StatusCell,
+    cancel_token: &CancelToken,
+    child: Option<ChildSlot>,
+    grace: Duration,
+    completion: &Signal,
+) {
+    let mut status_guard = status.lock();
+    if !status_guard.is_active() {
+        return;
+    }
+    *status_guard = WorldStatus::TimedOut;
+    drop(status_guard);
+
+    cancel_token.cancel();
+    completion.fire(); // 結果（期限切れエラー）を受け取れる状態になったことを通知する
+    if let Some(slot) = child {
+        thread::spawn(move || process::kill_with_grace(&slot, grace));
+    }
+}
+
 // DefaultトレイトはR型によって異なるため、一般的な実装は提供できない。
 // 特定のR型に対してのみDefaultを実装できる。
 // 例: impl Default for World<()> { ... }
@@ -423,12 +2320,74 @@ impl<R: Send + 'static> World<R> {
 pub trait AnyWorld: Send + Sync {
     /// Worldの現在の状態を取得します。
     fn any_progress(&self) -> WorldStatus;
+    /// 報告された進捗を取得します。
+    fn any_report(&self) -> Report;
+    /// 実行にかかった時間の記録を取得します。
+    fn any_metrics(&self) -> WorldMetrics;
+    /// 出力したログを取得します。
+    fn any_logs(&self) -> Vec<LogLine>;
+    /// アイテムの型が `item_type` であることを確認して、送り出されるアイテムを受け取る `Stream` を取り出します。
+    fn any_take_stream(
+        &self,
+        item_type: TypeId,
+        item_type_name: &'static str,
+    ) -> Result<Box<dyn Any + Send>, WorldError>;
+    /// メッセージの型を確認して、受信箱にメッセージを送ります。
+    fn any_send(
+        &self,
+        message: Box<dyn Any + Send>,
+        message_type_name: &'static str,
+    ) -> Result<(), WorldError>;
+    /// 出力したログを、出力されるそばから受け取るレシーバーを返します。
+    fn any_follow_logs(&self) -> mpsc::Receiver<LogLine>;
+    /// Worldが返す結果の型の名前を取得します（`std::any::type_name`）。
+    fn result_type_name(&self) -> &'static str;
     /// Worldを実行開始します。
-    fn any_start(&self) -> Result<(), String>;
+    fn any_start(&self) -> Result<(), WorldError>;
     /// Worldを停止します。
-    fn any_stop(&self) -> Result<(), String>;
+    fn any_stop(&self) -> Result<(), WorldError>;
+    /// Worldを強制終了します。
+    fn any_kill(&self) -> Result<(), WorldError>;
     /// Worldが完了するまで待機し、結果を`Box<dyn Any + Send>`として返します。
-    fn any_status(&self) -> Result<Box<dyn Any + Send>, String>;
+    fn any_status(&self) -> Result<Box<dyn Any + Send>, WorldError>;
+    /// 結果が既に得られていれば、ブロックせずに`Box<dyn Any + Send>`として返します。
+    fn any_try_status(&self) -> Option<Result<Box<dyn Any + Send>, WorldError>>;
+    /// 最大`timeout`の間だけ結果を待機し、`Box<dyn Any + Send>`として返します。
+    fn any_status_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Option<Result<Box<dyn Any + Send>, WorldError>>;
+    /// パニックで失敗した場合に、元のペイロードを取り出します。
+    fn any_take_panic_payload(&self) -> Option<Box<dyn Any + Send>>;
+    /// 結果が受け取れる状態になったときに一度だけ呼び出されるコールバックを登録します。
+    fn any_on_complete(&self, callback: Box<dyn FnOnce() + Send + 'static>);
+    /// 実行器で実行を待つ間の優先度を変更します。
+    fn any_set_priority(&self, priority: i32);
+    /// 状態が変化するたびに呼び出されるリスナーを設定します。
+    fn any_set_status_listener(&self, listener: StatusListener);
+    /// Worldを `Ready` 状態に戻します。
+    fn any_reset(&self) -> Result<(), WorldError>;
+    /// これまでの実行の最終状態を取得します。
+    fn any_history(&self) -> Vec<WorldStatus>;
+    /// `Ready` 状態のWorldを `Blocked` 状態にします。
+    fn any_block(&self) -> bool;
+    /// `Blocked` 状態のWorldを実行開始します。
+    fn any_unblock(&self) -> Result<(), WorldError>;
+    /// `Blocked` 状態のWorldを、実行せずに指定された状態で終了させます。
+    fn any_abandon(&self, status: WorldStatus);
+    /// 結果を受け取る上流のWorldのIDを取得します。
+    fn any_upstream_ids(&self) -> Vec<String>;
+    /// `index` 番目の上流のWorldの結果（値と型の名前）を、引数として渡します。
+    fn any_bind_input(
+        &self,
+        index: usize,
+        value: &(dyn Any + Send),
+        type_name: &'static str,
+    ) -> Result<(), WorldError>;
+    /// `index` 番目の上流のWorldの結果を既に受け取っていれば、渡したときの結果を返します。
+    fn any_input(&self, index: usize) -> Option<Result<(), WorldError>>;
+    /// 現在の実行の結果が送られる前に、その結果を参照する関数を登録します。既に送られていれば `false` を返します。
+    fn any_on_result(&self, tap: ResultTap) -> bool;
 }
 
 // World<R> が AnyWorld トレイトを実装するようにする
@@ -437,15 +2396,125 @@ impl<R: Send + 'static + Any> AnyWorld for World<R> {
         self.progress()
     }
 
-    fn any_start(&self) -> Result<(), String> {
+    fn any_report(&self) -> Report {
+        self.report()
+    }
+
+    fn any_metrics(&self) -> WorldMetrics {
+        self.metrics()
+    }
+
+    fn any_logs(&self) -> Vec<LogLine> {
+        self.logs()
+    }
+
+    fn any_take_stream(
+        &self,
+        item_type: TypeId,
+        item_type_name: &'static str,
+    ) -> Result<Box<dyn Any + Send>, WorldError> {
+        self.take_stream(item_type, item_type_name)
+    }
+
+    fn any_send(
+        &self,
+        message: Box<dyn Any + Send>,
+        message_type_name: &'static str,
+    ) -> Result<(), WorldError> {
+        self.send_any(message, message_type_name)
+    }
+
+    fn any_follow_logs(&self) -> mpsc::Receiver<LogLine> {
+        self.follow_logs()
+    }
+
+    fn result_type_name(&self) -> &'static str {
+        std::any::type_name::<R>()
+    }
+
+    fn any_start(&self) -> Result<(), WorldError> {
         self.start()
     }
 
-    fn any_stop(&self) -> Result<(), String> {
+    fn any_stop(&self) -> Result<(), WorldError> {
         self.stop()
     }
 
-    fn any_status(&self) -> Result<Box<dyn Any + Send>, String> {
+    fn any_kill(&self) -> Result<(), WorldError> {
+        self.kill()
+    }
+
+    fn any_status(&self) -> Result<Box<dyn Any + Send>, WorldError> {
         self.status().map(|r| Box::new(r) as Box<dyn Any + Send>)
     }
-}
\ No newline at end of file
+
+    fn any_try_status(&self) -> Option<Result<Box<dyn Any + Send>, WorldError>> {
+        self.try_status()
+            .map(|result| result.map(|r| Box::new(r) as Box<dyn Any + Send>))
+    }
+
+    fn any_status_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Option<Result<Box<dyn Any + Send>, WorldError>> {
+        self.status_timeout(timeout)
+            .map(|result| result.map(|r| Box::new(r) as Box<dyn Any + Send>))
+    }
+
+    fn any_take_panic_payload(&self) -> Option<Box<dyn Any + Send>> {
+        self.take_panic_payload()
+    }
+
+    fn any_on_complete(&self, callback: Box<dyn FnOnce() + Send + 'static>) {
+        self.on_complete(callback)
+    }
+
+    fn any_set_priority(&self, priority: i32) {
+        self.set_priority(priority)
+    }
+
+    fn any_set_status_listener(&self, listener: StatusListener) {
+        self.set_status_listener(listener)
+    }
+
+    fn any_reset(&self) -> Result<(), WorldError> {
+        self.reset()
+    }
+
+    fn any_history(&self) -> Vec<WorldStatus> {
+        self.history()
+    }
+
+    fn any_block(&self) -> bool {
+        self.block()
+    }
+
+    fn any_unblock(&self) -> Result<(), WorldError> {
+        self.unblock()
+    }
+
+    fn any_abandon(&self, status: WorldStatus) {
+        self.abandon(status)
+    }
+
+    fn any_upstream_ids(&self) -> Vec<String> {
+        self.upstream_ids()
+    }
+
+    fn any_bind_input(
+        &self,
+        index: usize,
+        value: &(dyn Any + Send),
+        type_name: &'static str,
+    ) -> Result<(), WorldError> {
+        self.bind_input(index, value, type_name)
+    }
+
+    fn any_input(&self, index: usize) -> Option<Result<(), WorldError>> {
+        self.input(index)
+    }
+
+    fn any_on_result(&self, tap: ResultTap) -> bool {
+        self.on_result(tap)
+    }
+}